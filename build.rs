@@ -0,0 +1,227 @@
+//! Generates the example tests of the solutions, one per example and part that records an
+//! answer. `solution!` includes `$OUT_DIR/examples.rs`, whose `example_tests!` macro expands to
+//! the tests of a day and part.
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+use toml::{Table, Value};
+
+/// Names that can't be used for a test, as they are keywords or the helper called by the tests.
+const RESERVED: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "check", "const", "continue",
+    "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if",
+    "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub",
+    "ref", "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// The examples of a day: their test names and whether they record an answer to each part, or
+/// [`None`] if the metadata is invalid.
+type Examples = Option<Vec<(String, [bool; 2])>>;
+
+fn main() {
+    println!("cargo:rerun-if-changed=Cargo.toml");
+    if Path::new("aoc.toml").exists() {
+        println!("cargo:rerun-if-changed=aoc.toml");
+    }
+
+    let (per_year, examples_dir) = read_config();
+    let mut days: Vec<(u16, u8, Examples)> = vec![];
+
+    if per_year {
+        // `data/examples` becomes `data/2024/examples`, see `Config::layout_day_dir`.
+        let (parent, name) = match (examples_dir.parent(), examples_dir.file_name()) {
+            (Some(parent), Some(name)) => (parent.to_path_buf(), Some(name.to_os_string())),
+            _ => (examples_dir.clone(), None),
+        };
+        let years = fs::read_dir(&parent)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let year = entry.file_name().to_str()?.parse::<u16>().ok()?;
+                Some((year, entry.path()))
+            });
+        let mut watched = false;
+        for (year, dir) in years {
+            let dir = match &name {
+                Some(name) => dir.join(name),
+                None => dir,
+            };
+            if dir.is_dir() {
+                watched = true;
+                watch(&dir);
+            }
+            days.extend(read_dir(&dir, |stem| Some((year, stem.parse().ok()?))));
+        }
+        if !watched {
+            watch(&parent);
+        }
+    } else {
+        watch(&examples_dir);
+        days.extend(read_dir(&examples_dir, |stem| {
+            let (year, day) = stem.split_once('-')?;
+            Some((year.parse().ok()?, day.parse().ok()?))
+        }));
+    }
+
+    days.sort_unstable_by_key(|(year, day, _)| (*year, *day));
+    let code = generate(&days);
+
+    let path = PathBuf::from(env::var_os("OUT_DIR").unwrap()).join("examples.rs");
+    // keep the file untouched if nothing changed, so that the solutions are not rebuilt.
+    if fs::read_to_string(&path).ok().as_deref() != Some(code.as_str()) {
+        fs::write(&path, code).unwrap();
+    }
+}
+
+/// Reads the layout and the examples folder from `aoc.toml`. The solutions report an invalid
+/// configuration, so it falls back to the defaults.
+fn read_config() -> (bool, PathBuf) {
+    let table: Table = fs::read_to_string("aoc.toml")
+        .ok()
+        .and_then(|config| config.parse().ok())
+        .unwrap_or_default();
+
+    let per_year = table.get("layout").and_then(Value::as_str) == Some("per-year");
+    let examples_dir = table
+        .get("data")
+        .and_then(|data| data.get("examples"))
+        .and_then(Value::as_str)
+        .unwrap_or("data/examples");
+
+    (per_year, PathBuf::from(examples_dir))
+}
+
+/// Reruns the build script if `path` changes, or its closest existing ancestor if it does not
+/// exist yet. Cargo reruns it on every build for paths that do not exist.
+fn watch(path: &Path) {
+    if let Some(path) = path
+        .ancestors()
+        .take_while(|path| !path.as_os_str().is_empty())
+        .find(|path| path.exists())
+    {
+        println!("cargo:rerun-if-changed={}", path.display());
+    }
+}
+
+/// Reads the metadata files in `dir`, finding the day of a file by its name with `parse_day`.
+fn read_dir(dir: &Path, parse_day: impl Fn(&str) -> Option<(u16, u8)>) -> Vec<(u16, u8, Examples)> {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "toml" {
+                return None;
+            }
+            let (year, day) = parse_day(path.file_stem()?.to_str()?)?;
+            let metadata = fs::read_to_string(&path).ok()?;
+            Some((year, day, read_examples(&metadata)))
+        })
+        .collect()
+}
+
+fn read_examples(metadata: &str) -> Examples {
+    let mut table: Table = metadata.parse().ok()?;
+    let examples = match table.remove("example") {
+        Some(Value::Array(examples)) => examples,
+        Some(_) => return None,
+        None => vec![],
+    };
+
+    let mut names = HashSet::new();
+    examples
+        .iter()
+        .enumerate()
+        .map(|(index, example)| {
+            let example = example.as_table()?;
+            let name = match example.get("name") {
+                Some(Value::String(name)) => name.clone(),
+                Some(_) => return None,
+                None => format!("example {}", index + 1),
+            };
+            let parts = ["part_one", "part_two"].map(|part| example.contains_key(part));
+            Some((test_name(&name, &mut names), parts))
+        })
+        .collect()
+}
+
+/// Turns the name of an example into a unique test name, e.g. `second example` into
+/// `second_example`.
+fn test_name(name: &str, names: &mut HashSet<String>) -> String {
+    let mut ident = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            ident.push(c.to_ascii_lowercase());
+        } else if !ident.is_empty() && !ident.ends_with('_') {
+            ident.push('_');
+        }
+    }
+    let ident = ident.trim_end_matches('_');
+
+    let ident =
+        if ident.starts_with(|c: char| c.is_ascii_alphabetic()) && !RESERVED.contains(&ident) {
+            ident.to_string()
+        } else if ident.is_empty() {
+            "example".to_string()
+        } else {
+            format!("example_{ident}")
+        };
+
+    let mut unique = ident.clone();
+    let mut n = 2;
+    while !names.insert(unique.clone()) {
+        unique = format!("{ident}_{n}");
+        n += 1;
+    }
+    unique
+}
+
+/// Generates `example_tests!`. It takes the year, day and part like `(2024, 5, part_one)`, and
+/// expands to the tests of the examples that record an answer to the part. Each test calls the
+/// function `check` with the index of its example, which the caller defines.
+fn generate(days: &[(u16, u8, Examples)]) -> String {
+    let mut code = String::from(
+        "/// Generated by `build.rs`, see `solution!`.\nmacro_rules! example_tests {\n",
+    );
+
+    for (year, day, examples) in days {
+        for (part, func) in ["part_one", "part_two"].into_iter().enumerate() {
+            let tests: Vec<(&str, usize)> = match examples {
+                Some(examples) => examples
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (_, parts))| parts[part])
+                    .map(|(index, (name, _))| (name.as_str(), index))
+                    .collect(),
+                // the test reads the metadata again and fails with its error.
+                None => vec![("invalid_metadata", 0)],
+            };
+            if tests.is_empty() {
+                continue;
+            }
+
+            writeln!(code, "    ({year}, {day}, {func}) => {{").unwrap();
+            for (name, index) in tests {
+                writeln!(code, "        #[test]\n        fn {name}() {{").unwrap();
+                writeln!(code, "            check({index});\n        }}").unwrap();
+            }
+            code.push_str("    };\n");
+        }
+
+        // solutions may pad the day, e.g. `solution!(2024, 05)`.
+        if *day < 10 {
+            writeln!(
+                code,
+                "    ({year}, 0{day}, $part:ident) => {{\n        example_tests!({year}, {day}, $part);\n    }};"
+            )
+            .unwrap();
+        }
+    }
+
+    code.push_str("    ($year:tt, $day:tt, $part:ident) => {};\n}\n");
+    code
+}
@@ -1,7 +1,10 @@
-advent_of_code::solution!(2024, 01);
+use advent_of_code::counter::Counter;
+use advent_of_code::template::answer::Answer;
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let (mut l, mut r): (Vec<u32>, Vec<u32>) = input
+advent_of_code::solution!(2024, 01, variants: [part_two::naive]);
+
+fn parse(input: &str) -> (Vec<u32>, Vec<u32>) {
+    input
         .lines()
         .map(|line| line.split_once("   ").unwrap())
         .map(|pair| {
@@ -10,7 +13,11 @@ pub fn part_one(input: &str) -> Option<u32> {
                 pair.1.parse::<u32>().unwrap(),
             )
         })
-        .unzip();
+        .unzip()
+}
+
+pub fn part_one(input: &str) -> Option<Answer> {
+    let (mut l, mut r) = parse(input);
 
     l.sort_unstable();
     r.sort_unstable();
@@ -18,38 +25,25 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(l.iter().zip(r).map(|(li, ri)| li.abs_diff(ri)).sum())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let (l, r): (Vec<u32>, Vec<u32>) = input
-        .lines()
-        .map(|line| line.split_once("   ").unwrap())
-        .map(|pair| {
-            (
-                pair.0.parse::<u32>().unwrap(),
-                pair.1.parse::<u32>().unwrap(),
-            )
-        })
-        .unzip();
+pub fn part_two(input: &str) -> Option<Answer> {
+    let (l, r) = parse(input);
+
+    let r = r.into_iter().collect::<Counter<_>>();
 
-    Some(
-        l.iter()
-            .map(|i| r.iter().filter(|j| i == *j).count() as u32 * *i)
-            .sum(),
-    )
+    Some(l.iter().map(|i| r.get(i) * u64::from(*i)).sum())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+mod part_two {
+    use advent_of_code::template::answer::Answer;
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11));
-    }
+    /// Scans the right list for every number of the left list, in O(n²).
+    pub fn naive(input: &str) -> Option<Answer> {
+        let (l, r) = super::parse(input);
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(31));
+        Some(
+            l.iter()
+                .map(|i| r.iter().filter(|j| *j == i).count() as u64 * u64::from(*i))
+                .sum(),
+        )
     }
 }
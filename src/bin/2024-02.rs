@@ -41,20 +41,3 @@ fn damped_safe_predicate(iter: &(impl Iterator<Item = u8> + Clone)) -> bool {
         .combinations(iter.clone().count() - 1)
         .any(|vec| safe_predicate(&vec.into_iter()))
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4));
-    }
-}
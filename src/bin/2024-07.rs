@@ -1,94 +1,86 @@
-advent_of_code::solution!(2024, 07);
+use advent_of_code::operators::{Op, Solver};
+use advent_of_code::template::crosscheck::{Crosscheck, Rng};
 
-pub fn part_one(input: &str) -> Option<i64> {
-    Some(
-        input
-            .lines()
-            .map(|line| line.split_once(": ").unwrap())
-            .map(|(left, right)| {
-                (
-                    left.parse::<i64>().unwrap(),
-                    right
-                        .split(' ')
-                        .map(|s| s.parse::<i64>().unwrap())
-                        .collect::<Vec<_>>(),
-                )
-            })
-            .filter(|(l, r)| valid((*l, r.clone())))
-            .map(|(left, _)| left)
-            .sum::<i64>(),
-    )
-}
+advent_of_code::solution!(2024, 07, crosscheck: crosscheck);
 
-pub fn part_two(input: &str) -> Option<i64> {
-    Some(
-        input
-            .lines()
-            .map(|line| line.split_once(": ").unwrap())
-            .map(|(left, right)| {
-                (
-                    left.parse::<i64>().unwrap(),
-                    right
-                        .split(' ')
-                        .map(|s| s.parse::<i64>().unwrap())
-                        .collect::<Vec<_>>(),
-                )
-            })
-            .filter(|(l, r)| valid_p2((*l, r.clone())))
-            .map(|(left, _)| left)
-            .sum::<i64>(),
-    )
+fn parse(input: &str) -> impl Iterator<Item = (u64, Vec<u64>)> + '_ {
+    input
+        .lines()
+        .map(|line| line.split_once(": ").unwrap())
+        .map(|(left, right)| {
+            (
+                left.parse::<u64>().unwrap(),
+                right
+                    .split(' ')
+                    .map(|s| s.parse::<u64>().unwrap())
+                    .collect::<Vec<_>>(),
+            )
+        })
 }
 
-fn valid((left, right): (i64, Vec<i64>)) -> bool {
-    if right.len() == 1 {
-        return left == right[0];
-    }
-
-    let new = *right.last().unwrap();
-    let next = right[..right.len() - 1].to_vec();
-
-    if left % new == 0 && valid((left / new, next.clone())) {
-        return true;
-    }
-
-    valid((left - new, next))
+fn calibration(input: &str, solver: Solver<Op>) -> u64 {
+    parse(input)
+        .filter(|(target, operands)| solver.is_solvable(*target, operands))
+        .map(|(target, _)| target)
+        .sum()
 }
 
-fn valid_p2((left, right): (i64, Vec<i64>)) -> bool {
-    if right.len() == 1 {
-        return left == right[0];
-    }
-
-    let new = *right.last().unwrap();
-    let next = right[..right.len() - 1].to_vec();
-
-    if (left - new) % 10i64.pow(new.ilog10() + 1) == 0
-        && valid_p2(((left - new) / 10i64.pow(new.ilog10() + 1), next.clone()))
-    {
-        return true;
-    }
-
-    if left % new == 0 && valid_p2((left / new, next.clone())) {
-        return true;
-    }
+pub fn part_one(input: &str) -> Option<u64> {
+    Some(calibration(input, Solver::new(&[Op::Add, Op::Mul])))
+}
 
-    valid_p2((left - new, next))
+pub fn part_two(input: &str) -> Option<u64> {
+    Some(calibration(
+        input,
+        Solver::new(&[Op::Add, Op::Mul, Op::Concat]),
+    ))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Compares the solver with trying every combination of operators.
+fn crosscheck() -> Crosscheck {
+    Crosscheck::new(generate)
+        .part_one(|input| Some(brute_force(input, 2)))
+        .part_two(|input| Some(brute_force(input, 3)))
+}
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3749));
-    }
+/// Equations with small operands, half of them solvable with some operators.
+fn generate(rng: &mut Rng) -> String {
+    (0..rng.range(1..8))
+        .map(|_| {
+            let operands: Vec<u64> = (0..rng.range(2..6)).map(|_| rng.range(1..20)).collect();
+            let target = if rng.bool() {
+                operands[1..]
+                    .iter()
+                    .fold(operands[0], |acc, &n| match rng.range(0..3) {
+                        0 => acc + n,
+                        1 => acc * n,
+                        _ => format!("{acc}{n}").parse().unwrap(),
+                    })
+            } else {
+                rng.range(1..1000)
+            };
+            let operands: Vec<String> = operands.iter().map(u64::to_string).collect();
+            format!("{target}: {}\n", operands.join(" "))
+        })
+        .collect()
+}
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(11387));
-    }
+/// Evaluates all combinations of the first `operators` of add, multiply and concatenate.
+fn brute_force(input: &str, operators: u32) -> u64 {
+    parse(input)
+        .filter(|(target, operands)| {
+            (0..operators.pow(operands.len() as u32 - 1)).any(|mut combination| {
+                operands[1..].iter().fold(operands[0], |acc, &n| {
+                    let op = combination % operators;
+                    combination /= operators;
+                    match op {
+                        0 => acc + n,
+                        1 => acc * n,
+                        _ => format!("{acc}{n}").parse().unwrap(),
+                    }
+                }) == *target
+            })
+        })
+        .map(|(target, _)| target)
+        .sum()
 }
@@ -1,116 +1,139 @@
-use itertools::Itertools;
+use std::iter;
 
-advent_of_code::solution!(2024, 09);
+use advent_of_code::segments::SegmentMap;
+use advent_of_code::template::crosscheck::{Crosscheck, Rng};
 
-pub fn part_one(input: &str) -> Option<i64> {
-    let mut empties = 0;
-    let mut data = input
+advent_of_code::solution!(2024, 09, crosscheck: crosscheck);
+
+fn parse(input: &str) -> SegmentMap<u64> {
+    input
         .trim_end()
         .char_indices()
         .map(|(i, c)| {
-            let num = c.to_digit(10).unwrap();
-            let repeater = if i % 2 == 0 {
-                i as i64 / 2
-            } else {
-                empties += num;
-                -1
-            };
-            std::iter::repeat(repeater).take(num as usize)
+            let len = c.to_digit(10).unwrap() as usize;
+            let id = if i % 2 == 0 { Some(i as u64 / 2) } else { None };
+            (len, id)
         })
-        .flatten()
-        .collect_vec();
+        .collect()
+}
 
-    while empties > 0 {
-        let l = data.iter().position(|x| *x == -1).unwrap();
-        let r = data.iter().rposition(|x| *x != -1).unwrap();
+pub fn part_one(input: &str) -> Option<u64> {
+    let mut disk = parse(input);
 
-        if l > r {
+    loop {
+        let (start, len, id) = disk
+            .segments()
+            .rev()
+            .find_map(|s| s.value.map(|id| (s.start, s.len, *id)))?;
+        let Some((free_start, free_len)) = disk.leftmost_fit(1) else {
+            break;
+        };
+
+        if free_start > start {
             break;
         }
 
-        data.swap(l, r);
-        empties -= 1;
+        let moved = free_len.min(len);
+        disk.set(free_start, moved, Some(id));
+        disk.set(start + len - moved, moved, None);
     }
 
-    Some(
-        data.iter()
-            .enumerate()
-            .filter(|(_, k)| **k != -1)
-            .map(|(i, k)| i as i64 * *k)
-            .sum(),
-    )
+    Some(disk.checksum(|id| *id))
 }
 
-pub fn part_two(input: &str) -> Option<i64> {
-    let mut data = input
-        .trim_end()
-        .char_indices()
-        .map(|(i, c)| {
-            let num = c.to_digit(10).unwrap();
-            let k = if i % 2 == 0 { i as i64 / 2 } else { -1 };
-            (num, k)
-        })
-        .collect_vec();
+pub fn part_two(input: &str) -> Option<u64> {
+    let mut disk = parse(input);
 
-    let (_, mut max) = data
-        .clone()
-        .iter()
-        .max_by(|(_, k), (_, l)| k.cmp(l))
-        .unwrap();
-    loop {
-        if max <= 0 {
-            break;
-        }
+    let files = disk
+        .segments()
+        .filter_map(|s| s.value.map(|id| (s.start, s.len, *id)))
+        .collect::<Vec<_>>();
 
-        let r = data
-            .iter()
-            .rposition(|(_, k)| *k != -1 && *k <= max)
-            .unwrap();
-        max -= 1;
-        let Some(l) = data
-            .iter()
-            .position(|(size, k)| *size >= data[r].0 && *k == -1)
-        else {
+    for (start, len, id) in files.into_iter().rev() {
+        let Some((free_start, _)) = disk.leftmost_fit(len) else {
             continue;
         };
 
-        if l > r {
+        if free_start > start {
             continue;
         }
 
-        data.swap(l, r);
-
-        if data[r].0 > data[l].0 {
-            let diff = data[l].0.abs_diff(data[r].0);
-            data[r].0 -= diff;
-            data.insert(l + 1, (diff, -1));
-        }
+        disk.set(free_start, len, Some(id));
+        disk.set(start, len, None);
     }
 
-    Some(
-        data.iter()
-            .map(|(repeat, k)| std::iter::repeat(*k).take(*repeat as usize))
-            .flatten()
-            .enumerate()
-            .filter(|(_, k)| *k != -1)
-            .map(|(i, k)| i as i64 * k)
-            .sum(),
-    )
+    Some(disk.checksum(|id| *id))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Compares the segment map with moving single blocks around.
+fn crosscheck() -> Crosscheck {
+    Crosscheck::new(generate)
+        .part_one(compact_blocks)
+        .part_two(compact_files)
+}
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1928));
+/// A short disk map, alternating files of 1 to 9 blocks and free space of 0 to 9 blocks.
+fn generate(rng: &mut Rng) -> String {
+    (0..rng.range(1..20))
+        .map(|i| {
+            let len = if i % 2 == 0 {
+                rng.range(1..10)
+            } else {
+                rng.range(0..10)
+            };
+            char::from_digit(len as u32, 10).unwrap()
+        })
+        .collect()
+}
+
+/// The id of the file of every block.
+fn blocks(input: &str) -> Vec<Option<u64>> {
+    input
+        .trim_end()
+        .chars()
+        .enumerate()
+        .flat_map(|(i, c)| {
+            let len = c.to_digit(10).unwrap() as usize;
+            assert!(i % 2 == 1 || len > 0, "files are never empty");
+            iter::repeat_n((i % 2 == 0).then_some(i as u64 / 2), len)
+        })
+        .collect()
+}
+
+fn checksum(blocks: &[Option<u64>]) -> u64 {
+    blocks
+        .iter()
+        .enumerate()
+        .filter_map(|(i, id)| id.map(|id| i as u64 * id))
+        .sum()
+}
+
+fn compact_blocks(input: &str) -> Option<u64> {
+    let mut blocks = blocks(input);
+    loop {
+        let free = blocks.iter().position(Option::is_none);
+        let last = blocks.iter().rposition(Option::is_some);
+        match (free, last) {
+            (Some(free), Some(last)) if free < last => blocks.swap(free, last),
+            _ => return Some(checksum(&blocks)),
+        }
     }
+}
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2858));
+fn compact_files(input: &str) -> Option<u64> {
+    let mut blocks = blocks(input);
+    let ids = blocks.iter().flatten().max().map_or(0, |max| max + 1);
+    for id in (0..ids).rev() {
+        let start = blocks.iter().position(|b| *b == Some(id)).unwrap();
+        let len = blocks.iter().filter(|b| **b == Some(id)).count();
+        if let Some(free) = blocks[..start]
+            .windows(len)
+            .position(|w| w.iter().all(Option::is_none))
+        {
+            for i in 0..len {
+                blocks.swap(free + i, start + i);
+            }
+        }
     }
+    Some(checksum(&blocks))
 }
@@ -1,72 +1,62 @@
-use pathfinding::directed::bfs::bfs_reach;
-use pathfinding::directed::count_paths::count_paths;
+use advent_of_code::grid::{search, Grid, Pos};
+use advent_of_code::visualize::{self, Color, Frame};
 
-advent_of_code::solution!(2024, 10);
+advent_of_code::solution!(2024, 10, validate: validate);
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let input = input.as_bytes();
-    let len = memchr::memchr(b'\n', input).unwrap() + 1;
-    let dirs = [-(len as isize), 1, len as isize, -1];
+    let grid = input.parse::<Grid<u8>>().unwrap();
 
     Some(
-        memchr::memchr_iter(b'0', input)
+        trailheads(&grid)
             .map(|pos| {
-                bfs_reach(pos, |&old| successors(input, old, &dirs))
-                    .filter(|&k| input[k] == b'9')
-                    .count() as u64
+                let reachable =
+                    search::flood_fill(&grid, pos, |from, to| grid[*to] == grid[*from] + 1);
+                visualize::record(|| {
+                    Frame::grid(&grid)
+                        .highlight(reachable.iter().copied(), Color::Blue)
+                        .highlight([pos], Color::Red)
+                        .caption(format!("Trailhead at {pos}"))
+                });
+
+                reachable.into_iter().filter(|k| grid[*k] == b'9').count() as u64
             })
             .sum(),
     )
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let input = input.as_bytes();
-    let len = memchr::memchr(b'\n', input).unwrap() + 1;
-    let dirs = [-(len as isize), 1, len as isize, -1];
+    let grid = input.parse::<Grid<u8>>().unwrap();
 
     Some(
-        memchr::memchr_iter(b'0', input)
+        trailheads(&grid)
             .map(|pos| {
-                count_paths(
+                search::count_paths(
+                    &grid,
                     pos,
-                    |&old| successors(input, old, &dirs),
-                    |k| input[*k] == b'9',
+                    |from, to| grid[*to] == grid[*from] + 1,
+                    |k| grid[*k] == b'9',
                 ) as u64
             })
             .sum(),
     )
 }
 
-fn successors<'a>(
-    input: &'a [u8],
-    from: usize,
-    dirs: &'a [isize; 4],
-) -> impl Iterator<Item = usize> + use<'a> {
-    let from_val = input[from];
-    dirs.into_iter().filter_map(move |&offset| {
-        let pos = from.checked_add_signed(offset)?;
-        let val = *input.get(pos)?;
-        if val == from_val + 1 {
-            Some(pos)
-        } else {
-            None
-        }
-    })
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(36));
+/// The parts assume a rectangular map of heights from 0 to 9.
+fn validate(input: &str) -> Result<(), String> {
+    let grid = input.parse::<Grid<u8>>().map_err(|e| e.to_string())?;
+    match grid.iter().find(|(_, height)| !height.is_ascii_digit()) {
+        Some((pos, height)) => Err(format!(
+            "expecting a height from 0 to 9 at row {}, column {}, found {:?}",
+            pos.y + 1,
+            pos.x + 1,
+            char::from(*height)
+        )),
+        None => Ok(()),
     }
+}
 
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(81));
-    }
+fn trailheads(grid: &Grid<u8>) -> impl Iterator<Item = Pos> + '_ {
+    grid.iter()
+        .filter(|(_, height)| **height == b'0')
+        .map(|(pos, _)| pos)
 }
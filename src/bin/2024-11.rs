@@ -1,89 +1,47 @@
-#![allow(static_mut_refs)]
+use advent_of_code::counter::Counter;
+use advent_of_code::digits::Digits;
 
-use std::{collections::HashMap, sync::OnceLock};
-
-advent_of_code::solution!(2024, 11);
-
-static mut CACHE: OnceLock<HashMap<(String, u32), u64>> = OnceLock::new();
-
-pub fn part_one(input: &str) -> Option<u64> {
-    unsafe {
-        CACHE.get_or_init(HashMap::new);
+advent_of_code::params! {
+    pub struct Params {
+        blinks_one: u32 = 25,
+        blinks_two: u32 = 75,
     }
-
-    Some(
-        input
-            .trim()
-            .split(' ')
-            .map(|com| com.to_string())
-            .map(|num| process(num.clone(), 25))
-            .sum(),
-    )
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    unsafe {
-        CACHE.get_or_init(HashMap::new);
-    }
+advent_of_code::solution!(2024, 11, params: Params);
 
-    Some(
-        input
-            .trim()
-            .split(' ')
-            .map(|com| com.to_string())
-            .map(|num| process(num.clone(), 75))
-            .sum(),
-    )
+pub fn part_one(input: &str, params: &Params) -> Option<u64> {
+    Some(blink(input, params.blinks_one))
 }
 
-fn process(num: String, iteration: u32) -> u64 {
-    if iteration == 0 {
-        return 1;
-    }
+pub fn part_two(input: &str, params: &Params) -> Option<u64> {
+    Some(blink(input, params.blinks_two))
+}
 
-    if let Some(result) = unsafe { CACHE.get().unwrap().get(&(num.clone(), iteration)) } {
-        return *result;
-    }
+fn blink(input: &str, iterations: u32) -> u64 {
+    let mut stones = input
+        .trim()
+        .split(' ')
+        .map(|num| num.parse::<u64>().unwrap())
+        .collect::<Counter<_>>();
 
-    let as_int = num.parse::<u64>().unwrap();
-    let result = if as_int == 0 {
-        process("1".to_string(), iteration - 1)
-    } else if num.len() % 2 == 0 {
-        process(
-            num[0..num.len() / 2]
-                .to_string()
-                .parse::<u64>()
-                .unwrap()
-                .to_string(),
-            iteration - 1,
-        ) + process(
-            num[num.len() / 2..num.len()]
-                .to_string()
-                .parse::<u64>()
-                .unwrap()
-                .to_string(),
-            iteration - 1,
-        )
-    } else {
-        process((as_int * 2024).to_string(), iteration - 1)
-    };
+    for _ in 0..iterations {
+        stones = stones.flat_map_keys(process);
+    }
 
-    *(unsafe {
-        CACHE
-            .get_mut()
-            .unwrap()
-            .entry((num, iteration))
-            .or_insert(result)
-    })
+    stones.total()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn process(num: u64) -> impl Iterator<Item = u64> {
+    let digits = num.digit_count();
+    let (first, second) = if num == 0 {
+        (1, None)
+    } else if digits % 2 == 0 {
+        let (left, right) = num.split_digits_at(digits / 2);
+        (left, Some(right))
+    } else {
+        (num * 2024, None)
+    };
 
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(55312));
-    }
+    std::iter::once(first).chain(second)
 }
@@ -0,0 +1,298 @@
+/// A multiset that counts how often each key occurs.
+use std::collections::hash_map::{self, HashMap};
+use std::hash::{BuildHasherDefault, Hash, Hasher};
+use std::ops::Index;
+
+/// Counts occurrences of keys.
+///
+/// Uses a multiplicative hasher instead of the default SipHash, which is considerably faster for
+/// the small integer keys that dominate AoC puzzles. It offers no protection against HashDoS.
+///
+/// ```
+/// # use advent_of_code::counter::Counter;
+/// let stones: Counter<u64> = [125, 17, 17].into_iter().collect();
+/// assert_eq!(stones.get(&17), 2);
+/// assert_eq!(stones.total(), 3);
+/// let halves = stones.map_keys(|stone| stone / 2);
+/// assert_eq!(halves.get(&8), 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Counter<T> {
+    counts: HashMap<T, u64, BuildHasherDefault<FxHasher>>,
+}
+
+impl<T: Eq + Hash> Counter<T> {
+    /// Creates an empty counter.
+    pub fn new() -> Self {
+        Self {
+            counts: HashMap::default(),
+        }
+    }
+
+    /// Creates an empty counter with space for at least `capacity` distinct keys.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            counts: HashMap::with_capacity_and_hasher(capacity, BuildHasherDefault::default()),
+        }
+    }
+
+    /// Counts one occurrence of `key`.
+    pub fn add(&mut self, key: T) {
+        self.add_n(key, 1);
+    }
+
+    /// Counts `n` occurrences of `key`.
+    pub fn add_n(&mut self, key: T, n: u64) {
+        if n > 0 {
+            *self.counts.entry(key).or_insert(0) += n;
+        }
+    }
+
+    /// Returns how often `key` was counted, `0` if never.
+    pub fn get(&self, key: &T) -> u64 {
+        self.counts.get(key).copied().unwrap_or(0)
+    }
+
+    /// Removes `key`, returning its count.
+    pub fn remove(&mut self, key: &T) -> u64 {
+        self.counts.remove(key).unwrap_or(0)
+    }
+
+    /// Number of distinct keys.
+    pub fn len(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+
+    /// Sum of all counts.
+    pub fn total(&self) -> u64 {
+        self.counts.values().sum()
+    }
+
+    /// Iterates `(key, count)` pairs in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&T, u64)> {
+        self.counts.iter().map(|(key, count)| (key, *count))
+    }
+
+    /// Iterates the distinct keys in arbitrary order.
+    pub fn keys(&self) -> impl Iterator<Item = &T> {
+        self.counts.keys()
+    }
+
+    /// Adds all counts of `other` to `self`.
+    pub fn merge(&mut self, other: Counter<T>) {
+        if other.len() > self.len() {
+            let mine = std::mem::replace(self, other);
+            return self.merge(mine);
+        }
+        for (key, count) in other {
+            self.add_n(key, count);
+        }
+    }
+
+    /// Maps every key, summing the counts of keys that map to the same value.
+    pub fn map_keys<U: Eq + Hash>(self, mut f: impl FnMut(T) -> U) -> Counter<U> {
+        let mut mapped = Counter::with_capacity(self.len());
+        for (key, count) in self {
+            mapped.add_n(f(key), count);
+        }
+        mapped
+    }
+
+    /// Maps every key to any number of keys, each inheriting the count of its origin.
+    pub fn flat_map_keys<U: Eq + Hash, I: IntoIterator<Item = U>>(
+        self,
+        mut f: impl FnMut(T) -> I,
+    ) -> Counter<U> {
+        let mut mapped = Counter::with_capacity(self.len());
+        for (key, count) in self {
+            for new_key in f(key) {
+                mapped.add_n(new_key, count);
+            }
+        }
+        mapped
+    }
+}
+
+impl<T: Eq + Hash + Ord> Counter<T> {
+    /// Returns all `(key, count)` pairs, highest count first. Ties are ordered by key.
+    pub fn most_common(&self) -> Vec<(&T, u64)> {
+        let mut entries: Vec<_> = self.iter().collect();
+        entries.sort_unstable_by(|(k1, c1), (k2, c2)| c2.cmp(c1).then_with(|| k1.cmp(k2)));
+        entries
+    }
+}
+
+impl<T: Eq + Hash> Default for Counter<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Eq + Hash> PartialEq for Counter<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.counts == other.counts
+    }
+}
+
+impl<T: Eq + Hash> Eq for Counter<T> {}
+
+impl<T: Eq + Hash> Index<&T> for Counter<T> {
+    type Output = u64;
+
+    /// Returns the count of `key`, `0` if never counted.
+    fn index(&self, key: &T) -> &Self::Output {
+        self.counts.get(key).unwrap_or(&0)
+    }
+}
+
+impl<T: Eq + Hash> FromIterator<T> for Counter<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut counter = Self::new();
+        counter.extend(iter);
+        counter
+    }
+}
+
+impl<T: Eq + Hash> Extend<T> for Counter<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for key in iter {
+            self.add(key);
+        }
+    }
+}
+
+impl<T: Eq + Hash> FromIterator<(T, u64)> for Counter<T> {
+    /// Builds a counter from `(key, count)` pairs, summing duplicate keys.
+    fn from_iter<I: IntoIterator<Item = (T, u64)>>(iter: I) -> Self {
+        let mut counter = Self::new();
+        for (key, count) in iter {
+            counter.add_n(key, count);
+        }
+        counter
+    }
+}
+
+impl<T> IntoIterator for Counter<T> {
+    type Item = (T, u64);
+    type IntoIter = hash_map::IntoIter<T, u64>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.counts.into_iter()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The hash function used by rustc (`FxHash`): a multiply-rotate per word.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FxHasher {
+    hash: u64,
+}
+
+const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+impl FxHasher {
+    #[inline]
+    fn add_to_hash(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+}
+
+impl Hasher for FxHasher {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.add_to_hash(u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+        for byte in chunks.remainder() {
+            self.add_to_hash(u64::from(*byte));
+        }
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.add_to_hash(u64::from(i));
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.add_to_hash(u64::from(i));
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.add_to_hash(u64::from(i));
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.add_to_hash(i);
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.add_to_hash(i as u64);
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Counter;
+
+    #[test]
+    fn counts_keys() {
+        let mut counter: Counter<u32> = [3, 4, 2, 1, 3, 3].into_iter().collect();
+        assert_eq!(counter.get(&3), 3);
+        assert_eq!(counter[&4], 1);
+        assert_eq!(counter.get(&5), 0);
+        assert_eq!(counter[&5], 0);
+        assert_eq!(counter.len(), 4);
+        assert_eq!(counter.total(), 6);
+
+        counter.add_n(5, 2);
+        counter.add_n(6, 0);
+        assert_eq!(counter.get(&5), 2);
+        assert_eq!(counter.len(), 5);
+        assert_eq!(counter.remove(&3), 3);
+        assert_eq!(counter.total(), 5);
+    }
+
+    #[test]
+    fn orders_most_common() {
+        let counter: Counter<char> = "abracadabra".chars().collect();
+        assert_eq!(
+            counter.most_common(),
+            vec![(&'a', 5), (&'b', 2), (&'r', 2), (&'c', 1), (&'d', 1)]
+        );
+    }
+
+    #[test]
+    fn merges_counters() {
+        let mut a: Counter<u8> = [1, 2, 2].into_iter().collect();
+        let b: Counter<u8> = [(2, 3), (3, 1), (3, 1)].into_iter().collect();
+        a.merge(b);
+        assert_eq!(a, [(1, 1), (2, 5), (3, 2)].into_iter().collect());
+    }
+
+    #[test]
+    fn maps_keys() {
+        let counter: Counter<u64> = [(10, 2), (11, 3), (20, 1)].into_iter().collect();
+        let tens = counter.clone().map_keys(|k| k / 10);
+        assert_eq!(tens, [(1, 5), (2, 1)].into_iter().collect());
+
+        let split = counter.flat_map_keys(|k| [k / 10, k % 10]);
+        assert_eq!(split, [(1, 8), (0, 3), (2, 1)].into_iter().collect());
+    }
+}
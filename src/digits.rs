@@ -0,0 +1,237 @@
+//! Integer helpers that work on the decimal (or any base) representation of a number,
+//! without round-tripping through strings.
+//!
+//! Signed integers are handled by their magnitude, i.e. the sign is never counted as a digit.
+//! Concatenation of negative numbers is not meaningful and yields unspecified results.
+
+/// Digit manipulation on primitive integers.
+///
+/// ```
+/// # use advent_of_code::digits::Digits;
+/// assert_eq!(253000u64.digit_count(), 6);
+/// assert_eq!(253000u64.split_digits_at(3), (253, 0));
+/// assert_eq!(12u64.concat(345), 12345);
+/// assert_eq!(12345u64.unconcat(345), Some(12));
+/// assert_eq!(6u64.digits(2).collect::<Vec<_>>(), vec![1, 1, 0]);
+/// ```
+pub trait Digits: Copy + Sized {
+    /// Number of decimal digits. Zero has one digit.
+    fn digit_count(self) -> u32 {
+        self.digit_count_radix(10)
+    }
+
+    /// Number of digits in base `radix`. Zero has one digit.
+    fn digit_count_radix(self, radix: u32) -> u32;
+
+    /// Splits the decimal representation after the first `mid` digits, e.g. `1234` at `1`
+    /// becomes `(1, 234)`. Leading zeros of the right half are dropped.
+    ///
+    /// # Panics
+    /// Panics if `mid` is larger than [`Digits::digit_count`].
+    fn split_digits_at(self, mid: u32) -> (Self, Self) {
+        self.checked_split_digits_at(mid)
+            .expect("split position should not exceed the digit count")
+    }
+
+    /// Same as [`Digits::split_digits_at`], returns [`None`] if `mid` is out of range.
+    fn checked_split_digits_at(self, mid: u32) -> Option<(Self, Self)>;
+
+    /// Appends the decimal digits of `suffix`, e.g. `12 || 345 = 12345`.
+    ///
+    /// # Panics
+    /// Panics if the result overflows.
+    fn concat(self, suffix: Self) -> Self {
+        self.checked_concat(suffix)
+            .expect("concatenation should not overflow")
+    }
+
+    /// Same as [`Digits::concat`], returns [`None`] on overflow.
+    fn checked_concat(self, suffix: Self) -> Option<Self>;
+
+    /// Inverse of [`Digits::concat`]: strips `suffix` from the end of the decimal digits,
+    /// returns [`None`] if `self` does not end with `suffix`.
+    fn unconcat(self, suffix: Self) -> Option<Self>;
+
+    /// Iterates the digits in base `radix`, most significant first.
+    fn digits(self, radix: u32) -> DigitIter;
+}
+
+/// The unsigned magnitude of an integer, so that digit helpers can ignore the sign.
+trait Magnitude {
+    type Unsigned;
+
+    fn magnitude(self) -> Self::Unsigned;
+}
+
+macro_rules! impl_magnitude {
+    ($($t:ty => $unsigned:ty, $f:expr);* $(;)?) => {$(
+        impl Magnitude for $t {
+            type Unsigned = $unsigned;
+
+            fn magnitude(self) -> Self::Unsigned {
+                $f(self)
+            }
+        }
+    )*};
+}
+
+impl_magnitude!(
+    u32 => u32, |v| v;
+    u64 => u64, |v| v;
+    u128 => u128, |v| v;
+    usize => usize, |v| v;
+    i32 => u32, i32::unsigned_abs;
+    i64 => u64, i64::unsigned_abs;
+    i128 => u128, i128::unsigned_abs;
+);
+
+macro_rules! impl_digits {
+    ($($t:ty),* $(,)?) => {$(
+        impl Digits for $t {
+            fn digit_count_radix(self, radix: u32) -> u32 {
+                self.magnitude().checked_ilog(radix as _).unwrap_or(0) + 1
+            }
+
+            fn checked_split_digits_at(self, mid: u32) -> Option<(Self, Self)> {
+                let count = self.digit_count();
+                if mid > count {
+                    return None;
+                }
+                let shift = <$t>::checked_pow(10, count - mid)?;
+                Some((self / shift, self % shift))
+            }
+
+            fn checked_concat(self, suffix: Self) -> Option<Self> {
+                let shift = <$t>::checked_pow(10, suffix.digit_count())?;
+                self.checked_mul(shift)?.checked_add(suffix)
+            }
+
+            fn unconcat(self, suffix: Self) -> Option<Self> {
+                let Some(shift) = <$t>::checked_pow(10, suffix.digit_count()) else {
+                    // only an empty prefix fits in front of such a long suffix.
+                    return (self == suffix).then_some(0);
+                };
+                let rest = self.checked_sub(suffix)?;
+                if rest % shift == 0 {
+                    Some(rest / shift)
+                } else {
+                    None
+                }
+            }
+
+            fn digits(self, radix: u32) -> DigitIter {
+                DigitIter::new(self.magnitude() as u128, radix)
+            }
+        }
+    )*};
+}
+
+impl_digits!(u32, u64, u128, usize, i32, i64, i128);
+
+/* -------------------------------------------------------------------------- */
+
+/// An iterator over the digits of a number, most significant first.
+/// Created by [`Digits::digits`].
+#[derive(Clone, Debug)]
+pub struct DigitIter {
+    value: u128,
+    divisor: u128,
+    radix: u128,
+    remaining: usize,
+}
+
+impl DigitIter {
+    fn new(value: u128, radix: u32) -> Self {
+        assert!(radix >= 2, "radix should be at least 2, got {radix}");
+        let count = value.checked_ilog(u128::from(radix)).unwrap_or(0);
+        Self {
+            value,
+            divisor: u128::from(radix).pow(count),
+            radix: u128::from(radix),
+            remaining: count as usize + 1,
+        }
+    }
+}
+
+impl Iterator for DigitIter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        // NOTE: every digit is smaller than the radix, which fits a u32.
+        #[allow(clippy::cast_possible_truncation)]
+        let digit = (self.value / self.divisor) as u32;
+        self.value %= self.divisor;
+        self.divisor /= self.radix;
+        Some(digit)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for DigitIter {}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Digits;
+
+    #[test]
+    fn counts_digits() {
+        assert_eq!(0u64.digit_count(), 1);
+        assert_eq!(9u64.digit_count(), 1);
+        assert_eq!(10u64.digit_count(), 2);
+        assert_eq!(u64::MAX.digit_count(), 20);
+        assert_eq!(u128::MAX.digit_count(), 39);
+        assert_eq!((-123i64).digit_count(), 3);
+        assert_eq!(i64::MIN.digit_count(), 19);
+        assert_eq!(255u64.digit_count_radix(16), 2);
+        assert_eq!(256u64.digit_count_radix(2), 9);
+    }
+
+    #[test]
+    fn splits_digits() {
+        assert_eq!(1234u64.split_digits_at(2), (12, 34));
+        assert_eq!(1000u64.split_digits_at(2), (10, 0));
+        assert_eq!(1234u64.split_digits_at(0), (0, 1234));
+        assert_eq!(1234u64.split_digits_at(4), (1234, 0));
+        assert_eq!(1234u64.checked_split_digits_at(5), None);
+        assert_eq!(u128::MAX.split_digits_at(1).0, 3);
+    }
+
+    #[test]
+    fn concatenates() {
+        assert_eq!(15u64.concat(6), 156);
+        assert_eq!(15u64.concat(0), 150);
+        assert_eq!(0u64.concat(15), 15);
+        assert_eq!(15i64.concat(60), 1560);
+        assert_eq!(u64::MAX.checked_concat(1), None);
+        assert_eq!(1u32.checked_concat(1_000_000_000), None);
+    }
+
+    #[test]
+    fn unconcatenates() {
+        assert_eq!(156u64.unconcat(6), Some(15));
+        assert_eq!(150u64.unconcat(0), Some(15));
+        assert_eq!(15u64.unconcat(15), Some(0));
+        assert_eq!(156u64.unconcat(7), None);
+        assert_eq!(6u64.unconcat(16), None);
+        assert_eq!(u64::MAX.unconcat(u64::MAX), Some(0));
+    }
+
+    #[test]
+    fn iterates_digits() {
+        assert_eq!(0u64.digits(10).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(1203u64.digits(10).collect::<Vec<_>>(), vec![1, 2, 0, 3]);
+        assert_eq!(10u64.digits(2).collect::<Vec<_>>(), vec![1, 0, 1, 0]);
+        assert_eq!(255u64.digits(16).collect::<Vec<_>>(), vec![15, 15]);
+        assert_eq!((-42i64).digits(10).collect::<Vec<_>>(), vec![4, 2]);
+        assert_eq!(u128::MAX.digits(10).len(), 39);
+    }
+}
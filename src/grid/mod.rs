@@ -0,0 +1,283 @@
+/// A dense 2D grid with positions, directions and search helpers.
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+pub mod search;
+
+/// A position on a grid. `x` grows to the right, `y` grows downwards.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Pos {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Pos {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    /// Returns the adjacent position in direction `dir`, [`None`] if it would be negative.
+    pub fn step(self, dir: Dir) -> Option<Self> {
+        let (dx, dy) = dir.delta();
+        Some(Self {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?,
+        })
+    }
+
+    /// Manhattan distance to `other`.
+    pub fn manhattan(self, other: Self) -> usize {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl Display for Pos {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// One of the four orthogonal directions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir {
+    /// All directions in clockwise order, starting with [`Dir::Up`].
+    pub const ALL: [Dir; 4] = [Dir::Up, Dir::Right, Dir::Down, Dir::Left];
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Dir::Up => Dir::Right,
+            Dir::Right => Dir::Down,
+            Dir::Down => Dir::Left,
+            Dir::Left => Dir::Up,
+        }
+    }
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Dir::Up => Dir::Left,
+            Dir::Left => Dir::Down,
+            Dir::Down => Dir::Right,
+            Dir::Right => Dir::Up,
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        self.turn_right().turn_right()
+    }
+
+    /// The `(dx, dy)` offset of one step in this direction.
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Dir::Up => (0, -1),
+            Dir::Right => (1, 0),
+            Dir::Down => (0, 1),
+            Dir::Left => (-1, 0),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A rectangular grid stored row by row.
+///
+/// ```
+/// # use advent_of_code::grid::{Grid, Pos};
+/// let grid: Grid<u8> = "ab\ncd\n".parse().unwrap();
+/// assert_eq!(grid[Pos::new(1, 0)], b'b');
+/// assert_eq!(grid.neighbours(Pos::new(0, 0)).count(), 2);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from row-major `cells`.
+    ///
+    /// # Panics
+    /// Panics if `cells` does not hold exactly `width * height` values.
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "cell count should match size");
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Creates a grid of the given size with every cell set to `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_vec(width, height, vec![value; width * height])
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.x < self.width && pos.y < self.height
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        if self.contains(pos) {
+            self.cells.get(pos.y * self.width + pos.x)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            self.cells.get_mut(pos.y * self.width + pos.x)
+        } else {
+            None
+        }
+    }
+
+    /// Iterates all positions row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Pos::new(x, y)))
+    }
+
+    /// Iterates all cells row by row together with their position.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Returns the first position (row by row) whose cell matches `predicate`.
+    pub fn find(&self, predicate: impl Fn(&T) -> bool) -> Option<Pos> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    /// Iterates the orthogonal neighbours of `pos` that lie on the grid.
+    pub fn neighbours(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Dir::ALL
+            .into_iter()
+            .filter_map(move |dir| pos.step(dir).filter(|next| self.contains(*next)))
+    }
+
+    /// Applies `f` to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::from_vec(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("position {pos} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {pos} is outside of the grid"))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Grid<u8> {
+    type Err = GridFromStrError;
+
+    /// Parses one row per line into a byte grid. All rows must have the same length.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cells = Vec::with_capacity(s.len());
+        let mut width = None;
+        let mut height = 0;
+
+        for line in s.lines() {
+            let line = line.trim_end_matches('\r').as_bytes();
+            if line.len() != *width.get_or_insert(line.len()) {
+                return Err(GridFromStrError { row: height });
+            }
+            cells.extend_from_slice(line);
+            height += 1;
+        }
+
+        Ok(Self::from_vec(width.unwrap_or(0), height, cells))
+    }
+}
+
+/// An error which can be returned when parsing a [`Grid`].
+#[derive(Debug)]
+pub struct GridFromStrError {
+    row: usize,
+}
+
+impl Error for GridFromStrError {}
+
+impl Display for GridFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "expecting rows of equal length, row {} differs from the first row",
+            self.row + 1
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Dir, Grid, Pos};
+
+    #[test]
+    fn parses_grids() {
+        let grid: Grid<u8> = "abc\r\ndef\n".parse().unwrap();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[Pos::new(2, 1)], b'f');
+        assert_eq!(grid.get(Pos::new(3, 0)), None);
+        assert_eq!(grid.find(|c| *c == b'e'), Some(Pos::new(1, 1)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_ragged_rows() {
+        "abc\nde".parse::<Grid<u8>>().unwrap();
+    }
+
+    #[test]
+    fn steps_within_bounds() {
+        let grid = Grid::filled(2, 2, 0);
+        assert_eq!(Pos::new(0, 0).step(Dir::Up), None);
+        assert_eq!(Pos::new(0, 0).step(Dir::Right), Some(Pos::new(1, 0)));
+        assert_eq!(
+            grid.neighbours(Pos::new(1, 1)).collect::<Vec<_>>(),
+            vec![Pos::new(1, 0), Pos::new(0, 1)]
+        );
+    }
+
+    #[test]
+    fn turns() {
+        assert_eq!(Dir::Up.turn_right(), Dir::Right);
+        assert_eq!(Dir::Up.turn_left(), Dir::Left);
+        assert_eq!(Dir::Left.reverse(), Dir::Right);
+    }
+}
@@ -0,0 +1,265 @@
+/// Adapters that run `pathfinding` searches directly on a [`Grid`].
+///
+/// Searches are generic over the search state via [`Node`]: plain positions ([`Pos`]) move to
+/// their orthogonal neighbours, while `(Pos, Dir)` states either step forward or turn in place,
+/// which is what "reindeer maze" style puzzles need. Passability and cost closures receive the
+/// state moved from and the state moved to; a cost of [`None`] marks a move as impossible.
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+use pathfinding::directed::{astar, bfs, count_paths, dijkstra};
+
+use super::{Dir, Grid, Pos};
+
+/// A search state on a grid.
+pub trait Node: Copy + Eq + Hash {
+    /// The position of this state.
+    fn pos(&self) -> Pos;
+
+    /// States reachable in one move, before applying passability or costs.
+    fn candidates<T>(&self, grid: &Grid<T>) -> impl Iterator<Item = Self>;
+}
+
+impl Node for Pos {
+    fn pos(&self) -> Pos {
+        *self
+    }
+
+    fn candidates<T>(&self, grid: &Grid<T>) -> impl Iterator<Item = Self> {
+        grid.neighbours(*self)
+    }
+}
+
+impl Node for (Pos, Dir) {
+    fn pos(&self) -> Pos {
+        self.0
+    }
+
+    fn candidates<T>(&self, grid: &Grid<T>) -> impl Iterator<Item = Self> {
+        let (pos, dir) = *self;
+        let forward = pos
+            .step(dir)
+            .filter(|next| grid.contains(*next))
+            .map(|next| (next, dir));
+        forward
+            .into_iter()
+            .chain([(pos, dir.turn_left()), (pos, dir.turn_right())])
+    }
+}
+
+fn passable_successors<'a, T, N: Node>(
+    grid: &'a Grid<T>,
+    passable: &'a impl Fn(&N, &N) -> bool,
+) -> impl Fn(&N) -> Vec<N> + 'a {
+    move |from| {
+        from.candidates(grid)
+            .filter(|to| passable(from, to))
+            .collect()
+    }
+}
+
+fn weighted_successors<'a, T, N: Node>(
+    grid: &'a Grid<T>,
+    cost: &'a impl Fn(&N, &N) -> Option<u64>,
+) -> impl Fn(&N) -> Vec<(N, u64)> + 'a {
+    move |from| {
+        from.candidates(grid)
+            .filter_map(|to| cost(from, &to).map(|c| (to, c)))
+            .collect()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Computes the number of moves from `start` to every reachable state.
+pub fn bfs_distances<T, N: Node>(
+    grid: &Grid<T>,
+    start: N,
+    passable: impl Fn(&N, &N) -> bool,
+) -> HashMap<N, usize> {
+    let mut distances = HashMap::from([(start, 0)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(from) = queue.pop_front() {
+        let distance = distances[&from] + 1;
+        for to in from.candidates(grid) {
+            if passable(&from, &to) && !distances.contains_key(&to) {
+                distances.insert(to, distance);
+                queue.push_back(to);
+            }
+        }
+    }
+
+    distances
+}
+
+/// Returns a path with the fewest moves from `start` to a state matching `goal`,
+/// including both ends.
+pub fn bfs_path<T, N: Node>(
+    grid: &Grid<T>,
+    start: N,
+    passable: impl Fn(&N, &N) -> bool,
+    goal: impl Fn(&N) -> bool,
+) -> Option<Vec<N>> {
+    bfs::bfs(&start, passable_successors(grid, &passable), goal)
+}
+
+/// Returns every state reachable from `start`, including `start`, in BFS order.
+pub fn flood_fill<T, N: Node>(
+    grid: &Grid<T>,
+    start: N,
+    passable: impl Fn(&N, &N) -> bool,
+) -> Vec<N> {
+    bfs::bfs_reach(start, passable_successors(grid, &passable)).collect()
+}
+
+/// Counts the distinct paths from `start` to states matching `goal`.
+///
+/// The moves allowed by `passable` must not form cycles, e.g. strictly ascending heights.
+pub fn count_paths<T, N: Node>(
+    grid: &Grid<T>,
+    start: N,
+    passable: impl Fn(&N, &N) -> bool,
+    goal: impl Fn(&N) -> bool,
+) -> usize {
+    count_paths::count_paths(start, passable_successors(grid, &passable), goal)
+}
+
+/// Returns a cheapest path from `start` to a state matching `goal` and its cost.
+pub fn dijkstra_path<T, N: Node>(
+    grid: &Grid<T>,
+    start: N,
+    cost: impl Fn(&N, &N) -> Option<u64>,
+    goal: impl Fn(&N) -> bool,
+) -> Option<(Vec<N>, u64)> {
+    dijkstra::dijkstra(&start, weighted_successors(grid, &cost), goal)
+}
+
+/// Returns a cheapest path from `start` to any state at position `target` and its cost.
+///
+/// Uses the manhattan distance as heuristic, so every move that changes the position
+/// must cost at least `1`.
+pub fn astar_path<T, N: Node>(
+    grid: &Grid<T>,
+    start: N,
+    cost: impl Fn(&N, &N) -> Option<u64>,
+    target: Pos,
+) -> Option<(Vec<N>, u64)> {
+    astar::astar(
+        &start,
+        weighted_successors(grid, &cost),
+        |node| node.pos().manhattan(target) as u64,
+        |node| node.pos() == target,
+    )
+}
+
+/// Returns every cheapest path from `start` to a state matching `goal` and their common cost.
+///
+/// The number of paths can grow exponentially with the size of open areas.
+pub fn all_shortest_paths<T, N: Node>(
+    grid: &Grid<T>,
+    start: N,
+    cost: impl Fn(&N, &N) -> Option<u64>,
+    goal: impl Fn(&N) -> bool,
+) -> Option<(Vec<Vec<N>>, u64)> {
+    astar::astar_bag(&start, weighted_successors(grid, &cost), |_| 0, goal)
+        .map(|(paths, cost)| (paths.collect(), cost))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        all_shortest_paths, astar_path, bfs_distances, bfs_path, count_paths, dijkstra_path,
+        flood_fill,
+    };
+    use crate::grid::{Dir, Grid, Pos};
+
+    fn get_mock_maze() -> Grid<u8> {
+        "#######\n\
+         #S...##\n\
+         #.##.##\n\
+         #....E#\n\
+         #######"
+            .parse()
+            .unwrap()
+    }
+
+    fn open(grid: &Grid<u8>) -> impl Fn(&Pos, &Pos) -> bool + '_ {
+        |_, to| grid[*to] != b'#'
+    }
+
+    #[test]
+    fn computes_distance_maps() {
+        let grid = get_mock_maze();
+        let distances = bfs_distances(&grid, Pos::new(1, 1), open(&grid));
+        assert_eq!(distances[&Pos::new(1, 1)], 0);
+        assert_eq!(distances[&Pos::new(4, 3)], 5);
+        assert_eq!(distances[&Pos::new(5, 3)], 6);
+        assert_eq!(distances.len(), 11);
+    }
+
+    #[test]
+    fn reconstructs_paths() {
+        let grid = get_mock_maze();
+        let path = bfs_path(&grid, Pos::new(1, 1), open(&grid), |p| grid[*p] == b'E').unwrap();
+        assert_eq!(path.len(), 7);
+        assert_eq!(path.first(), Some(&Pos::new(1, 1)));
+        assert_eq!(path.last(), Some(&Pos::new(5, 3)));
+    }
+
+    #[test]
+    fn fills_regions() {
+        let grid = get_mock_maze();
+        assert_eq!(flood_fill(&grid, Pos::new(1, 1), open(&grid)).len(), 11);
+        assert_eq!(
+            flood_fill(&grid, Pos::new(0, 0), |_, to| grid[*to] == b'#').len(),
+            22
+        );
+    }
+
+    #[test]
+    fn counts_and_enumerates_shortest_paths() {
+        let grid = get_mock_maze();
+        let cost = |_: &Pos, to: &Pos| (grid[*to] != b'#').then_some(1);
+        let (paths, cost) =
+            all_shortest_paths(&grid, Pos::new(1, 1), cost, |p| *p == Pos::new(4, 3)).unwrap();
+        assert_eq!(cost, 5);
+        assert_eq!(paths.len(), 2);
+        assert_eq!(
+            count_paths(
+                &grid,
+                Pos::new(1, 1),
+                |from, to| grid[*to] != b'#'
+                    && to.manhattan(Pos::new(1, 1)) > from.manhattan(Pos::new(1, 1)),
+                |p| *p == Pos::new(4, 3)
+            ),
+            2
+        );
+    }
+
+    #[test]
+    fn searches_directed_states() {
+        let grid = get_mock_maze();
+        // moving costs 1, turning costs 1000.
+        let cost = |from: &(Pos, Dir), to: &(Pos, Dir)| {
+            if from.0 == to.0 {
+                Some(1000)
+            } else {
+                (grid[to.0] != b'#').then_some(1)
+            }
+        };
+        let start = (Pos::new(1, 1), Dir::Right);
+        let end = Pos::new(5, 3);
+
+        let (path, total) = dijkstra_path(&grid, start, cost, |n| n.0 == end).unwrap();
+        assert_eq!(total, 2006);
+        assert_eq!(path.last().unwrap().0, end);
+        assert_eq!(astar_path(&grid, start, cost, end).unwrap().1, 2006);
+
+        let (paths, total) = all_shortest_paths(&grid, start, cost, |n| n.0 == end).unwrap();
+        assert_eq!(total, 2006);
+        assert_eq!(paths.len(), 2);
+    }
+}
@@ -3,3 +3,12 @@
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
+pub mod counter;
+pub mod digits;
+pub mod grid;
+pub mod ocr;
+pub mod operators;
+pub mod ordering;
+pub mod segments;
+pub mod visualize;
+pub mod years;
@@ -1,4 +1,7 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, crosscheck, download, inputs, migrate_layout, new_year, read, scaffold, solve, status,
+    time, watch_day,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -7,12 +10,16 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
+    use advent_of_code::template::config::Layout;
+    use advent_of_code::template::input::InputSource;
+    use advent_of_code::template::playback::{Playback, DEFAULT_FPS};
     use advent_of_code::template::Day;
     use std::process;
 
     pub enum AppArguments {
         Download {
             day: Day,
+            profile: Option<String>,
         },
         Read {
             day: Day,
@@ -21,20 +28,54 @@ mod args {
             day: Day,
             download: bool,
             overwrite: bool,
+            template: Option<String>,
+            year_module: bool,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            part: Option<u8>,
+            variants: bool,
+            params: Vec<String>,
+            input: InputSource,
+            playback: Option<Playback>,
         },
         All {
             release: bool,
+            profiles: bool,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            part: Option<u8>,
+        },
+        WatchDay {
+            day: Day,
+            submit: Option<u8>,
+        },
+        Crosscheck {
+            day: Day,
+            release: bool,
+            iterations: u64,
+            seed: Option<u64>,
+            part: Option<u8>,
+        },
+        Status {
+            year: Option<u16>,
+            json: bool,
+        },
+        NewYear {
+            year: u16,
+            scaffold: bool,
+        },
+        MigrateLayout {
+            layout: Layout,
+        },
+        Inputs {
+            lock: bool,
         },
         #[cfg(feature = "today")]
         Today,
@@ -46,19 +87,23 @@ mod args {
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                profiles: args.contains("--profiles"),
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let part = args.opt_value_from_str("--part")?;
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    part,
                 }
             }
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
+                profile: args.opt_value_from_str("--profile")?,
             },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
@@ -67,12 +112,94 @@ mod args {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                template: args.opt_value_from_str("--template")?,
+                year_module: args.contains("--year-module"),
             },
-            Some("solve") => AppArguments::Solve {
+            Some("solve") => {
+                let day = args.free_from_str()?;
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let part = args.opt_value_from_str("--part")?;
+                let variants = args.contains("--variants");
+                let dhat = args.contains("--dhat");
+                let params = args.values_from_str("--param")?;
+                let playback = if args.contains("--visualize") {
+                    Some(Playback {
+                        fps: args.opt_value_from_str("--fps")?.unwrap_or(DEFAULT_FPS),
+                        export: args.opt_value_from_str("--export")?,
+                        format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                    })
+                } else {
+                    None
+                };
+                let path: Option<String> = args.opt_value_from_str("--input")?;
+                let stdin = args.contains("--stdin");
+                // `--example` takes an optional number, which is the only free argument left.
+                let example = if args.contains("--example") {
+                    Some(args.opt_free_from_str()?.unwrap_or(1))
+                } else {
+                    None
+                };
+
+                let input = match (path, stdin, example) {
+                    (None, false, None) => InputSource::Puzzle,
+                    (Some(path), false, None) => InputSource::Path(path.into()),
+                    (None, true, None) => InputSource::Stdin,
+                    (None, false, Some(n)) => InputSource::Example(n),
+                    _ => {
+                        eprintln!(
+                            "Only one of --input, --stdin and --example can be used at a time."
+                        );
+                        process::exit(1);
+                    }
+                };
+
+                if submit.is_some() && !matches!(input, InputSource::Puzzle) {
+                    eprintln!("--submit can only be used with the puzzle input.");
+                    process::exit(1);
+                }
+
+                AppArguments::Solve {
+                    day,
+                    release,
+                    dhat,
+                    submit,
+                    part,
+                    variants,
+                    params,
+                    input,
+                    playback,
+                }
+            }
+            Some("watch-day") => AppArguments::WatchDay {
                 day: args.free_from_str()?,
-                release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+            },
+            Some("crosscheck") => AppArguments::Crosscheck {
+                day: args.free_from_str()?,
+                release: args.contains("--release"),
+                iterations: args.opt_value_from_str("--iterations")?.unwrap_or(100),
+                seed: args.opt_value_from_str("--seed")?,
+                part: args.opt_value_from_str("--part")?,
+            },
+            Some("new-year") => AppArguments::NewYear {
+                year: args.free_from_str()?,
+                scaffold: args.contains("--scaffold"),
+            },
+            Some("migrate-layout") => AppArguments::MigrateLayout {
+                layout: args.free_from_str()?,
+            },
+            Some("inputs") => match args.free_from_str::<String>()?.as_str() {
+                "lock" => AppArguments::Inputs { lock: true },
+                "unlock" => AppArguments::Inputs { lock: false },
+                x => {
+                    eprintln!("Unknown inputs action: {x}, expecting \"lock\" or \"unlock\".");
+                    process::exit(1);
+                }
+            },
+            Some("status") => AppArguments::Status {
+                year: args.opt_value_from_str("--year")?,
+                json: args.contains("--json"),
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -102,34 +229,75 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
+            AppArguments::All { release, profiles } => all::handle(release, profiles),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                part,
+            } => time::handle(day, all, store, part),
+            AppArguments::Download { day, profile } => download::handle(day, profile.as_deref()),
             AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download, overwrite } => {
-                scaffold::handle(day, overwrite);
+            AppArguments::Scaffold {
+                day,
+                download,
+                overwrite,
+                template,
+                year_module,
+            } => {
+                // download first, so that the template can use the puzzle title.
                 if download {
-                    download::handle(day);
+                    download::handle_before_scaffold(day);
                 }
+                scaffold::offer_year_module(day.into_inner().0, year_module);
+                scaffold::handle(day, overwrite, template.as_deref());
             }
             AppArguments::Solve {
                 day,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                part,
+                variants,
+                params,
+                input,
+                playback,
+            } => solve::handle(
+                day,
+                release,
+                dhat,
+                submit,
+                part,
+                variants,
+                &params,
+                &input,
+                playback.as_ref(),
+            ),
+            AppArguments::WatchDay { day, submit } => watch_day::handle(day, submit),
+            AppArguments::Crosscheck {
+                day,
+                release,
+                iterations,
+                seed,
+                part,
+            } => crosscheck::handle(day, release, iterations, seed, part),
+            AppArguments::Status { year, json } => status::handle(year, json),
+            AppArguments::NewYear { year, scaffold } => new_year::handle(year, scaffold),
+            AppArguments::MigrateLayout { layout } => migrate_layout::handle(layout),
+            AppArguments::Inputs { lock } => inputs::handle(lock),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
+                        download::handle_before_scaffold(day);
+                        scaffold::offer_year_module(day.into_inner().0, false);
+                        scaffold::handle(day, false, None);
                         read::handle(day)
                     }
                     None => {
                         eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            "`today` command can only be run during an event in december. \
+                            Please use `scaffold` with a specific day."
                         );
                         process::exit(1)
                     }
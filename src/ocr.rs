@@ -0,0 +1,278 @@
+/// Recognizes the block letters that some puzzles render as their answer.
+///
+/// Supports the two fonts used by AoC: letters that are 6 rows high (mostly 4 columns wide)
+/// and letters that are 10 rows high (6 columns wide). Lit pixels are `#` or `█`, everything
+/// else is treated as dark. Letters are separated by at least one dark column.
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+const FONT_6: [(char, [&str; 6]); 18] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+#[rustfmt::skip]
+const FONT_10: [(char, [&str; 10]); 15] = [
+    ('A', ["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', ["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', [".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', ["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', [".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', ["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', ["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', ["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', ["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', ["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', ["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', ["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', ["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', ["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+/// Maps the normalized pattern of a glyph (see [`Pixels::glyph_key`]) to its letter.
+fn glyphs() -> &'static HashMap<String, char> {
+    static GLYPHS: OnceLock<HashMap<String, char>> = OnceLock::new();
+    GLYPHS.get_or_init(|| {
+        let small = FONT_6.iter().map(|(c, rows)| (*c, rows.as_slice()));
+        let large = FONT_10.iter().map(|(c, rows)| (*c, rows.as_slice()));
+        small
+            .chain(large)
+            .map(|(c, rows)| {
+                // glyphs are keyed without their surrounding dark columns.
+                let pixels = Pixels::from_rows(rows.iter().copied());
+                (pixels.glyph_key(pixels.segments()[0].clone()), c)
+            })
+            .collect()
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A dense bitmap, trimmed to the rows that contain lit pixels.
+struct Pixels {
+    rows: Vec<Vec<bool>>,
+    width: usize,
+}
+
+impl Pixels {
+    fn from_rows<'a>(lines: impl Iterator<Item = &'a str>) -> Self {
+        let mut rows: Vec<Vec<bool>> = lines
+            .map(|line| line.chars().map(|c| c == '#' || c == '█').collect())
+            .collect();
+
+        while rows.last().is_some_and(|row| !row.contains(&true)) {
+            rows.pop();
+        }
+        let leading = rows.iter().take_while(|row| !row.contains(&true)).count();
+        rows.drain(..leading);
+
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        for row in &mut rows {
+            row.resize(width, false);
+        }
+
+        Self { rows, width }
+    }
+
+    fn is_column_dark(&self, x: usize) -> bool {
+        self.rows.iter().all(|row| !row[x])
+    }
+
+    /// Splits the columns into runs that are separated by dark columns.
+    fn segments(&self) -> Vec<std::ops::Range<usize>> {
+        let mut segments = vec![];
+        let mut start = None;
+        for x in 0..=self.width {
+            let dark = x == self.width || self.is_column_dark(x);
+            match (start, dark) {
+                (None, false) => start = Some(x),
+                (Some(s), true) => {
+                    segments.push(s..x);
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        segments
+    }
+
+    fn glyph_key(&self, columns: std::ops::Range<usize>) -> String {
+        self.rows
+            .iter()
+            .map(|row| {
+                row[columns.clone()]
+                    .iter()
+                    .map(|lit| if *lit { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Recognizes the letters drawn in `art`, one pixel row per line.
+///
+/// ```
+/// # use advent_of_code::ocr::recognize;
+/// let art = "#..#.###.\n#..#..#..\n####..#..\n#..#..#..\n#..#..#..\n#..#.###.";
+/// assert_eq!(recognize(art).unwrap(), "HI");
+/// ```
+pub fn recognize(art: &str) -> Result<String, OcrError> {
+    recognize_pixels(&Pixels::from_rows(art.lines()))
+}
+
+/// Recognizes the letters drawn by a set of lit `(x, y)` points.
+pub fn recognize_points(
+    points: impl IntoIterator<Item = (usize, usize)>,
+) -> Result<String, OcrError> {
+    let points: Vec<_> = points.into_iter().collect();
+    let width = points.iter().map(|(x, _)| x + 1).max().unwrap_or(0);
+    let height = points.iter().map(|(_, y)| y + 1).max().unwrap_or(0);
+
+    let mut rows = vec![vec![false; width]; height];
+    for (x, y) in points {
+        rows[y][x] = true;
+    }
+
+    let lines: Vec<String> = rows
+        .iter()
+        .map(|row| row.iter().map(|lit| if *lit { '#' } else { '.' }).collect())
+        .collect();
+    recognize(&lines.join("\n"))
+}
+
+fn recognize_pixels(pixels: &Pixels) -> Result<String, OcrError> {
+    match pixels.rows.len() {
+        0 => return Err(OcrError::Empty),
+        6 | 10 => {}
+        height => return Err(OcrError::UnsupportedHeight(height)),
+    }
+
+    pixels
+        .segments()
+        .into_iter()
+        .enumerate()
+        .map(|(index, columns)| {
+            let key = pixels.glyph_key(columns);
+            glyphs()
+                .get(&key)
+                .copied()
+                .ok_or(OcrError::UnknownGlyph { index, glyph: key })
+        })
+        .collect()
+}
+
+/// An error which can be returned when recognizing letters.
+#[derive(Debug, PartialEq, Eq)]
+pub enum OcrError {
+    /// The art does not contain any lit pixel.
+    Empty,
+    /// The letters are neither 6 nor 10 rows high.
+    UnsupportedHeight(usize),
+    /// The glyph at `index` (counting from zero) is not part of the font.
+    UnknownGlyph { index: usize, glyph: String },
+}
+
+impl Error for OcrError {}
+
+impl Display for OcrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OcrError::Empty => write!(f, "no letters found."),
+            OcrError::UnsupportedHeight(height) => {
+                write!(
+                    f,
+                    "expecting letters 6 or 10 rows high, found {height} rows."
+                )
+            }
+            OcrError::UnknownGlyph { index, glyph } => {
+                write!(f, "unknown letter at position {}:\n{glyph}", index + 1)
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{recognize, recognize_points, OcrError, FONT_10, FONT_6};
+
+    fn render<const N: usize>(font: &[(char, [&str; N])], text: &str, spacing: &str) -> String {
+        (0..N)
+            .map(|y| {
+                text.chars()
+                    .map(|c| font.iter().find(|(l, _)| *l == c).unwrap().1[y])
+                    .collect::<Vec<_>>()
+                    .join(spacing)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn recognizes_small_font() {
+        let letters: String = FONT_6.iter().map(|(c, _)| *c).collect();
+        assert_eq!(recognize(&render(&FONT_6, &letters, ".")).unwrap(), letters);
+    }
+
+    #[test]
+    fn recognizes_large_font() {
+        let letters: String = FONT_10.iter().map(|(c, _)| *c).collect();
+        assert_eq!(
+            recognize(&render(&FONT_10, &letters, "..")).unwrap(),
+            letters
+        );
+    }
+
+    #[test]
+    fn handles_padding_and_block_characters() {
+        let art = render(&FONT_6, "ZJ", " ")
+            .replace('#', "█")
+            .replace('.', " ");
+        assert_eq!(recognize(&format!("\n\n{art}\n\n")).unwrap(), "ZJ");
+    }
+
+    #[test]
+    fn recognizes_points() {
+        let art = render(&FONT_6, "EH", ".");
+        let points = art.lines().enumerate().flat_map(|(y, row)| {
+            row.chars()
+                .enumerate()
+                .filter(|(_, c)| *c == '#')
+                .map(move |(x, _)| (x + 3, y + 2))
+        });
+        assert_eq!(recognize_points(points).unwrap(), "EH");
+    }
+
+    #[test]
+    fn reports_errors() {
+        assert_eq!(recognize("...\n..."), Err(OcrError::Empty));
+        assert_eq!(recognize("#\n#"), Err(OcrError::UnsupportedHeight(2)));
+        assert!(matches!(
+            recognize(&render(&FONT_6, "A", ".").replace(".##.", "####")),
+            Err(OcrError::UnknownGlyph { index: 0, .. })
+        ));
+    }
+}
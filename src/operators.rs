@@ -0,0 +1,234 @@
+/// Search for operator assignments that make `a ? b ? c ... = target` hold.
+/// Expressions are evaluated strictly left to right, as in most AoC equation puzzles.
+use std::fmt::Display;
+
+use crate::digits::Digits;
+
+/// The result of inverting an operator, i.e. solving `lhs ? rhs = target` for `lhs`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Inverse {
+    /// No `lhs` satisfies the equation.
+    None,
+    /// Exactly this `lhs` satisfies the equation.
+    Exactly(u64),
+    /// Every `lhs` satisfies the equation (e.g. `lhs * 0 = 0`).
+    Any,
+}
+
+/// A binary operator that can be evaluated forwards and inverted for backward pruning.
+pub trait Operator: Copy {
+    /// Evaluates `lhs ? rhs`, returns [`None`] if the result is not representable.
+    fn apply(self, lhs: u64, rhs: u64) -> Option<u64>;
+
+    /// Solves `lhs ? rhs = target` for `lhs`.
+    fn invert(self, target: u64, rhs: u64) -> Inverse;
+}
+
+/// The operators used by AoC equation puzzles.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Op {
+    Add,
+    Mul,
+    /// Decimal concatenation, e.g. `12 || 345 = 12345`.
+    Concat,
+}
+
+impl Operator for Op {
+    fn apply(self, lhs: u64, rhs: u64) -> Option<u64> {
+        match self {
+            Op::Add => lhs.checked_add(rhs),
+            Op::Mul => lhs.checked_mul(rhs),
+            Op::Concat => lhs.checked_concat(rhs),
+        }
+    }
+
+    fn invert(self, target: u64, rhs: u64) -> Inverse {
+        match self {
+            Op::Add => target
+                .checked_sub(rhs)
+                .map_or(Inverse::None, Inverse::Exactly),
+            Op::Mul => match (target, rhs) {
+                (0, 0) => Inverse::Any,
+                (_, 0) => Inverse::None,
+                _ if target % rhs == 0 => Inverse::Exactly(target / rhs),
+                _ => Inverse::None,
+            },
+            Op::Concat => target.unconcat(rhs).map_or(Inverse::None, Inverse::Exactly),
+        }
+    }
+}
+
+impl Display for Op {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Op::Add => "+",
+            Op::Mul => "*",
+            Op::Concat => "||",
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Evaluates `operands` left to right, combining them with `ops`.
+/// Returns [`None`] on overflow or if the number of operators does not match.
+pub fn evaluate<O: Operator>(operands: &[u64], ops: &[O]) -> Option<u64> {
+    let (first, rest) = operands.split_first()?;
+    if rest.len() != ops.len() {
+        return None;
+    }
+    rest.iter()
+        .zip(ops)
+        .try_fold(*first, |acc, (rhs, op)| op.apply(acc, *rhs))
+}
+
+/// Searches operator assignments over a fixed set of operators.
+///
+/// The search runs backwards from the target: the last operand is peeled off by inverting
+/// each operator, which prunes most branches early. No allocation happens during the search.
+///
+/// ```
+/// # use advent_of_code::operators::{Op, Solver};
+/// let solver = Solver::new(&[Op::Add, Op::Mul]);
+/// assert!(solver.is_solvable(3267, &[81, 40, 27]));
+/// assert_eq!(solver.count(3267, &[81, 40, 27]), 2);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Solver<'a, O> {
+    ops: &'a [O],
+}
+
+impl<'a, O: Operator> Solver<'a, O> {
+    /// Creates a solver that may use any of `ops` between two operands.
+    pub const fn new(ops: &'a [O]) -> Self {
+        Self { ops }
+    }
+
+    /// Returns `true` if some operator assignment evaluates `operands` to `target`.
+    pub fn is_solvable(&self, target: u64, operands: &[u64]) -> bool {
+        self.solve_into(target, operands, &mut [])
+    }
+
+    /// Returns an operator assignment that evaluates `operands` to `target`, if one exists.
+    pub fn solve(&self, target: u64, operands: &[u64]) -> Option<Vec<O>> {
+        if operands.is_empty() {
+            return None;
+        }
+        let mut assignment = vec![self.ops.first().copied()?; operands.len() - 1];
+        if self.solve_into(target, operands, &mut assignment) {
+            Some(assignment)
+        } else {
+            None
+        }
+    }
+
+    /// Counts the operator assignments that evaluate `operands` to `target`.
+    pub fn count(&self, target: u64, operands: &[u64]) -> u64 {
+        if operands.is_empty() {
+            return 0;
+        }
+        self.count_rec(target, operands)
+    }
+
+    /// Backward search that records the chosen operators into `assignment`, if it is non-empty.
+    fn solve_into(&self, target: u64, operands: &[u64], assignment: &mut [O]) -> bool {
+        let Some((last, rest)) = operands.split_last() else {
+            return false;
+        };
+        if rest.is_empty() {
+            return target == *last;
+        }
+
+        for op in self.ops {
+            let found = match op.invert(target, *last) {
+                Inverse::None => false,
+                Inverse::Exactly(lhs) => self.solve_into(lhs, rest, assignment),
+                Inverse::Any => {
+                    // any prefix works, so pick the first operator everywhere.
+                    assignment.iter_mut().for_each(|slot| *slot = self.ops[0]);
+                    true
+                }
+            };
+            if found {
+                if let Some(slot) = assignment.get_mut(rest.len() - 1) {
+                    *slot = *op;
+                }
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn count_rec(&self, target: u64, operands: &[u64]) -> u64 {
+        let Some((last, rest)) = operands.split_last() else {
+            return 0;
+        };
+        if rest.is_empty() {
+            return u64::from(target == *last);
+        }
+
+        self.ops
+            .iter()
+            .map(|op| match op.invert(target, *last) {
+                Inverse::None => 0,
+                Inverse::Exactly(lhs) => self.count_rec(lhs, rest),
+                Inverse::Any => (self.ops.len() as u64).saturating_pow((rest.len() - 1) as u32),
+            })
+            .sum()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{evaluate, Inverse, Op, Operator, Solver};
+
+    const PART_ONE: Solver<Op> = Solver::new(&[Op::Add, Op::Mul]);
+    const PART_TWO: Solver<Op> = Solver::new(&[Op::Add, Op::Mul, Op::Concat]);
+
+    #[test]
+    fn inverts_concatenation() {
+        assert_eq!(Op::Concat.invert(156, 6), Inverse::Exactly(15));
+        assert_eq!(Op::Concat.invert(1510, 10), Inverse::Exactly(15));
+        assert_eq!(Op::Concat.invert(150, 0), Inverse::Exactly(15));
+        assert_eq!(Op::Concat.invert(156, 7), Inverse::None);
+        assert_eq!(Op::Concat.invert(6, 16), Inverse::None);
+        assert_eq!(Op::Concat.apply(15, 6), Some(156));
+        assert_eq!(Op::Concat.apply(u64::MAX, 1), None);
+    }
+
+    #[test]
+    fn solves_equations() {
+        assert!(PART_ONE.is_solvable(190, &[10, 19]));
+        assert!(!PART_ONE.is_solvable(156, &[15, 6]));
+        assert!(PART_TWO.is_solvable(156, &[15, 6]));
+        assert!(PART_TWO.is_solvable(7290, &[6, 8, 6, 15]));
+        assert!(!PART_TWO.is_solvable(21037, &[9, 7, 18, 13]));
+    }
+
+    #[test]
+    fn returns_valid_assignments() {
+        let ops = PART_TWO.solve(7290, &[6, 8, 6, 15]).unwrap();
+        assert_eq!(ops, vec![Op::Mul, Op::Concat, Op::Mul]);
+        assert_eq!(evaluate(&[6, 8, 6, 15], &ops), Some(7290));
+        assert_eq!(PART_ONE.solve(83, &[17, 5]), None);
+    }
+
+    #[test]
+    fn counts_solutions() {
+        assert_eq!(PART_ONE.count(3267, &[81, 40, 27]), 2);
+        assert_eq!(PART_ONE.count(292, &[11, 6, 16, 20]), 1);
+        assert_eq!(PART_ONE.count(0, &[5, 3, 0]), 2);
+        assert_eq!(PART_ONE.count(1, &[1]), 1);
+        assert_eq!(PART_ONE.count(1, &[]), 0);
+    }
+
+    #[test]
+    fn handles_zero_operands() {
+        let ops = PART_ONE.solve(0, &[5, 3, 0]).unwrap();
+        assert_eq!(evaluate(&[5, 3, 0], &ops), Some(0));
+        assert!(!PART_ONE.is_solvable(1, &[5, 3, 0]));
+    }
+}
@@ -0,0 +1,364 @@
+/// Precedence rules (`a|b`, "a must come before b") and topological sorting.
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::str::FromStr;
+
+/// A directed graph of "must come before" rules between values.
+///
+/// ```
+/// # use advent_of_code::ordering::PrecedenceGraph;
+/// let rules: PrecedenceGraph<u8> = "47|53\n97|47".parse().unwrap();
+/// assert!(rules.is_ordered(&[97, 47, 53]));
+/// assert_eq!(rules.sort(&[53, 47, 97]).unwrap(), vec![97, 47, 53]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PrecedenceGraph<T> {
+    index: HashMap<T, usize>,
+    nodes: Vec<T>,
+    successors: Vec<Vec<usize>>,
+}
+
+impl<T: Copy + Eq + Hash> PrecedenceGraph<T> {
+    /// Creates a graph without any rules.
+    pub fn new() -> Self {
+        Self {
+            index: HashMap::new(),
+            nodes: Vec::new(),
+            successors: Vec::new(),
+        }
+    }
+
+    /// Adds the rule that `before` must come before `after`.
+    pub fn add_rule(&mut self, before: T, after: T) {
+        let from = self.node(before);
+        let to = self.node(after);
+        if !self.successors[from].contains(&to) {
+            self.successors[from].push(to);
+        }
+    }
+
+    /// Returns `true` if there is a rule stating that `before` must come before `after`.
+    pub fn must_precede(&self, before: T, after: T) -> bool {
+        match (self.index.get(&before), self.index.get(&after)) {
+            (Some(from), Some(to)) => self.successors[*from].contains(to),
+            _ => false,
+        }
+    }
+
+    /// Checks whether `seq` violates no rule between the values it contains.
+    ///
+    /// Rules mentioning values that are not part of `seq` are ignored. Fails with
+    /// [`OrderingError::Duplicate`] if a value with rules occurs twice in `seq`.
+    pub fn check(&self, seq: &[T]) -> Result<(), OrderingError<T>> {
+        let positions = self.positions(seq)?;
+
+        for (pos, id) in seq.iter().enumerate() {
+            let Some(id) = self.index.get(id) else {
+                continue;
+            };
+            for next in &self.successors[*id] {
+                if let Some(&next_pos) = positions.get(next)
+                    && next_pos < pos
+                {
+                    return Err(OrderingError::Violation {
+                        before: self.nodes[*id],
+                        after: self.nodes[*next],
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if `seq` is consistent with the rules, see [`PrecedenceGraph::check`].
+    pub fn is_ordered(&self, seq: &[T]) -> bool {
+        self.check(seq).is_ok()
+    }
+
+    /// Sorts `seq` topologically in `O(n + rules between its values)`.
+    ///
+    /// Values that are not constrained relative to each other keep their relative input order
+    /// where possible. Fails with [`OrderingError::Cycle`] if the rules between the values
+    /// of `seq` contain a cycle, and with [`OrderingError::Duplicate`] like
+    /// [`PrecedenceGraph::check`].
+    pub fn sort(&self, seq: &[T]) -> Result<Vec<T>, OrderingError<T>> {
+        let positions = self.positions(seq)?;
+
+        // in-degree per position in `seq`, counting only edges within the subset.
+        let mut in_degree = vec![0usize; seq.len()];
+        for id in seq.iter().filter_map(|v| self.index.get(v)) {
+            for next in &self.successors[*id] {
+                if let Some(&pos) = positions.get(next) {
+                    in_degree[pos] += 1;
+                }
+            }
+        }
+
+        let mut queue: VecDeque<usize> = (0..seq.len()).filter(|&i| in_degree[i] == 0).collect();
+        let mut sorted = Vec::with_capacity(seq.len());
+
+        while let Some(pos) = queue.pop_front() {
+            sorted.push(seq[pos]);
+            let Some(id) = self.index.get(&seq[pos]) else {
+                continue;
+            };
+            for next in &self.successors[*id] {
+                if let Some(&next_pos) = positions.get(next) {
+                    in_degree[next_pos] -= 1;
+                    if in_degree[next_pos] == 0 {
+                        queue.push_back(next_pos);
+                    }
+                }
+            }
+        }
+
+        if sorted.len() == seq.len() {
+            Ok(sorted)
+        } else {
+            Err(OrderingError::Cycle(
+                self.find_cycle(seq, &positions, &in_degree),
+            ))
+        }
+    }
+
+    fn node(&mut self, value: T) -> usize {
+        *self.index.entry(value).or_insert_with(|| {
+            self.nodes.push(value);
+            self.successors.push(Vec::new());
+            self.nodes.len() - 1
+        })
+    }
+
+    /// Maps node ids of the values in `seq` to their position in `seq`.
+    fn positions(&self, seq: &[T]) -> Result<HashMap<usize, usize>, OrderingError<T>> {
+        let mut positions = HashMap::with_capacity(seq.len());
+        for (pos, value) in seq.iter().enumerate() {
+            if let Some(id) = self.index.get(value)
+                && positions.insert(*id, pos).is_some()
+            {
+                return Err(OrderingError::Duplicate(*value));
+            }
+        }
+        Ok(positions)
+    }
+
+    /// Every value left over by the sort still has a predecessor among the left over values,
+    /// so walking predecessors backwards is guaranteed to run into a cycle.
+    fn find_cycle(
+        &self,
+        seq: &[T],
+        positions: &HashMap<usize, usize>,
+        in_degree: &[usize],
+    ) -> Vec<T> {
+        let mut predecessor: HashMap<usize, usize> = HashMap::new();
+        for pos in (0..seq.len()).filter(|&pos| in_degree[pos] > 0) {
+            let id = self.index[&seq[pos]];
+            for next in &self.successors[id] {
+                if positions.get(next).is_some_and(|&pos| in_degree[pos] > 0) {
+                    predecessor.insert(*next, id);
+                }
+            }
+        }
+
+        let Some(mut current) = predecessor.keys().next().copied() else {
+            return vec![];
+        };
+
+        let mut path: Vec<usize> = vec![];
+        let mut seen: HashMap<usize, usize> = HashMap::new();
+
+        while !seen.contains_key(&current) {
+            seen.insert(current, path.len());
+            path.push(current);
+            current = predecessor[&current];
+        }
+
+        let mut cycle: Vec<T> = path[seen[&current]..]
+            .iter()
+            .rev()
+            .map(|id| self.nodes[*id])
+            .collect();
+
+        // start the cycle at its first value in `seq` to keep the output deterministic.
+        let first = (0..cycle.len())
+            .min_by_key(|&i| positions[&self.index[&cycle[i]]])
+            .unwrap_or(0);
+        cycle.rotate_left(first);
+        cycle
+    }
+}
+
+impl<T: Copy + Eq + Hash> Default for PrecedenceGraph<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Copy + Eq + Hash> FromIterator<(T, T)> for PrecedenceGraph<T> {
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(iter: I) -> Self {
+        let mut graph = Self::new();
+        for (before, after) in iter {
+            graph.add_rule(before, after);
+        }
+        graph
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl<T: Copy + Eq + Hash + FromStr> FromStr for PrecedenceGraph<T> {
+    type Err = RuleFromStrError;
+
+    /// Parses one `a|b` rule per line, ignoring empty lines.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let (before, after) = line
+                    .trim()
+                    .split_once('|')
+                    .ok_or_else(|| RuleFromStrError(line.into()))?;
+                Ok((
+                    before.parse().map_err(|_| RuleFromStrError(line.into()))?,
+                    after.parse().map_err(|_| RuleFromStrError(line.into()))?,
+                ))
+            })
+            .collect()
+    }
+}
+
+/// An error which can be returned when parsing a [`PrecedenceGraph`].
+#[derive(Debug)]
+pub struct RuleFromStrError(String);
+
+impl Error for RuleFromStrError {}
+
+impl Display for RuleFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting a rule of the form `a|b`, got `{}`", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned when checking or sorting a sequence.
+#[derive(Debug, PartialEq, Eq)]
+pub enum OrderingError<T> {
+    /// The rule `before|after` is violated by the sequence.
+    Violation { before: T, after: T },
+    /// The rules between the values of the sequence contain this cycle.
+    Cycle(Vec<T>),
+    /// The value occurs more than once in the sequence, so its position is ambiguous.
+    Duplicate(T),
+}
+
+impl<T: std::fmt::Debug + Display> Error for OrderingError<T> {}
+
+impl<T: Display> Display for OrderingError<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OrderingError::Violation { before, after } => {
+                write!(f, "rule `{before}|{after}` is violated.")
+            }
+            OrderingError::Cycle(cycle) => {
+                f.write_str("rules contain a cycle: ")?;
+                for value in cycle {
+                    write!(f, "{value} -> ")?;
+                }
+                match cycle.first() {
+                    Some(first) => write!(f, "{first}."),
+                    None => f.write_str("<unknown>."),
+                }
+            }
+            OrderingError::Duplicate(value) => {
+                write!(f, "value `{value}` occurs more than once.")
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{OrderingError, PrecedenceGraph};
+
+    fn get_mock_rules() -> PrecedenceGraph<u8> {
+        "47|53\n97|13\n97|61\n97|47\n75|29\n61|13\n75|53\n29|13\n97|29\n53|29\n61|53\n\
+        97|53\n61|29\n47|13\n75|47\n97|75\n47|61\n75|61\n47|29\n75|13\n53|13"
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    fn accepts_ordered_sequences() {
+        let rules = get_mock_rules();
+        assert!(rules.is_ordered(&[75, 47, 61, 53, 29]));
+        assert!(rules.is_ordered(&[97, 61, 53, 29, 13]));
+        assert!(rules.is_ordered(&[75, 29, 13]));
+    }
+
+    #[test]
+    fn names_violated_rule() {
+        let rules = get_mock_rules();
+        assert_eq!(
+            rules.check(&[75, 97, 47, 61, 53]),
+            Err(OrderingError::Violation {
+                before: 97,
+                after: 75
+            })
+        );
+    }
+
+    #[test]
+    fn ignores_unknown_values() {
+        let rules = get_mock_rules();
+        assert!(rules.is_ordered(&[1, 75, 2, 29]));
+        assert_eq!(rules.sort(&[29, 1, 75]).unwrap(), vec![1, 75, 29]);
+    }
+
+    #[test]
+    fn sorts_subsets() {
+        let rules = get_mock_rules();
+        assert_eq!(
+            rules.sort(&[75, 97, 47, 61, 53]).unwrap(),
+            vec![97, 75, 47, 61, 53]
+        );
+        assert_eq!(rules.sort(&[61, 13, 29]).unwrap(), vec![61, 29, 13]);
+        assert_eq!(
+            rules.sort(&[97, 13, 75, 29, 47]).unwrap(),
+            vec![97, 75, 47, 29, 13]
+        );
+    }
+
+    #[test]
+    fn reports_cycles() {
+        let rules: PrecedenceGraph<u8> = [(1, 2), (2, 3), (3, 1), (0, 1)].into_iter().collect();
+        assert!(rules.is_ordered(&[1, 2]));
+        assert_eq!(rules.sort(&[1, 2]).unwrap(), vec![1, 2]);
+        assert_eq!(
+            rules.sort(&[0, 3, 2, 1]),
+            Err(OrderingError::Cycle(vec![3, 1, 2]))
+        );
+    }
+
+    #[test]
+    fn rejects_duplicate_values() {
+        let rules = get_mock_rules();
+        assert_eq!(
+            rules.check(&[75, 47, 75]),
+            Err(OrderingError::Duplicate(75))
+        );
+        assert_eq!(rules.sort(&[29, 75, 29]), Err(OrderingError::Duplicate(29)));
+        assert!(rules.is_ordered(&[1, 75, 1, 29]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_rules() {
+        "1|2\n3-4".parse::<PrecedenceGraph<u8>>().unwrap();
+    }
+}
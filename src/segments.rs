@@ -0,0 +1,314 @@
+/// Run-length storage of a linear space (e.g. disk blocks) as free or occupied segments.
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A run of blocks that are either free or hold the same value. Free segments are maximal, while
+/// adjacent occupied segments may hold equal values, e.g. after [`SegmentMap::split_at`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Segment<'a, T> {
+    pub start: usize,
+    pub len: usize,
+    pub value: Option<&'a T>,
+}
+
+impl<T> Segment<'_, T> {
+    /// The position right after the last block of this segment.
+    pub fn end(&self) -> usize {
+        self.start + self.len
+    }
+
+    pub fn is_free(&self) -> bool {
+        self.value.is_none()
+    }
+}
+
+/// Partitions the blocks `0..len()` into segments that are free or hold a value.
+///
+/// Adjacent free segments are always merged. Free segments are additionally indexed by their
+/// length, so that [`SegmentMap::leftmost_fit`] runs in `O(d log n)` where `d` is the number
+/// of distinct free segment lengths, which is tiny for AoC inputs.
+///
+/// ```
+/// # use advent_of_code::segments::SegmentMap;
+/// let mut disk: SegmentMap<u64> = [(2, Some(0)), (3, None), (1, Some(1))].into_iter().collect();
+/// let (start, _) = disk.leftmost_fit(1).unwrap();
+/// disk.set(start, 1, Some(1));
+/// disk.set(5, 1, None);
+/// assert_eq!(disk.checksum(|id| *id), 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SegmentMap<T> {
+    /// segment start -> (length, value).
+    spans: BTreeMap<usize, (usize, Option<T>)>,
+    /// free segment length -> starts of free segments with this length.
+    free: BTreeMap<usize, BTreeSet<usize>>,
+    len: usize,
+}
+
+impl<T: Clone> SegmentMap<T> {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self {
+            spans: BTreeMap::new(),
+            free: BTreeMap::new(),
+            len: 0,
+        }
+    }
+
+    /// Total number of blocks, free or occupied.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Appends `len` blocks holding `value` (or free blocks if [`None`]). Empty runs are ignored.
+    pub fn push(&mut self, len: usize, value: Option<T>) {
+        if len == 0 {
+            return;
+        }
+        let start = self.len;
+        self.len += len;
+        self.insert(start, len, value);
+        self.merge_free(start);
+    }
+
+    /// Returns the segment containing block `pos`.
+    pub fn get(&self, pos: usize) -> Option<Segment<'_, T>> {
+        if pos >= self.len {
+            return None;
+        }
+        self.spans
+            .range(..=pos)
+            .next_back()
+            .map(|(start, (len, value))| Segment {
+                start: *start,
+                len: *len,
+                value: value.as_ref(),
+            })
+    }
+
+    /// Iterates all segments in order.
+    pub fn segments(&self) -> impl DoubleEndedIterator<Item = Segment<'_, T>> {
+        self.spans.iter().map(|(start, (len, value))| Segment {
+            start: *start,
+            len: *len,
+            value: value.as_ref(),
+        })
+    }
+
+    /// Iterates every occupied block as `(position, value)`.
+    pub fn blocks(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.segments()
+            .filter_map(|segment| {
+                let value = segment.value?;
+                Some((segment.start..segment.end()).map(move |pos| (pos, value)))
+            })
+            .flatten()
+    }
+
+    /// Returns `(start, len)` of the leftmost free segment that is at least `min_len` long.
+    pub fn leftmost_fit(&self, min_len: usize) -> Option<(usize, usize)> {
+        self.free
+            .range(min_len.max(1)..)
+            .filter_map(|(len, starts)| starts.first().map(|start| (*start, *len)))
+            .min()
+    }
+
+    /// Ensures that a segment starts at `pos`, splitting the segment containing it.
+    pub fn split_at(&mut self, pos: usize) {
+        let Some(segment) = self.get(pos) else {
+            return;
+        };
+        if segment.start == pos {
+            return;
+        }
+        let start = segment.start;
+        let (len, value) = self.remove(start);
+        self.insert(start, pos - start, value.clone());
+        self.insert(pos, start + len - pos, value);
+    }
+
+    /// Overwrites the blocks `start..start + len` with `value`, or frees them if [`None`].
+    ///
+    /// # Panics
+    /// Panics if the range exceeds [`SegmentMap::len`].
+    pub fn set(&mut self, start: usize, len: usize, value: Option<T>) {
+        assert!(
+            start + len <= self.len,
+            "range {start}..{} is out of bounds for {} blocks",
+            start + len,
+            self.len
+        );
+        if len == 0 {
+            return;
+        }
+
+        self.split_at(start);
+        self.split_at(start + len);
+
+        while let Some(pos) = self.spans.range(start..start + len).next().map(|(k, _)| *k) {
+            self.remove(pos);
+        }
+
+        self.insert(start, len, value);
+        self.merge_free(start);
+    }
+
+    /// Sums `position * weight(value)` over all occupied blocks, in `O(segments)`.
+    pub fn checksum(&self, weight: impl Fn(&T) -> u64) -> u64 {
+        self.segments()
+            .filter_map(|segment| {
+                let value = segment.value?;
+                let (start, len) = (segment.start as u64, segment.len as u64);
+                // sum of start..start + len
+                Some(weight(value) * (len * start + len * (len - 1) / 2))
+            })
+            .sum()
+    }
+
+    fn insert(&mut self, start: usize, len: usize, value: Option<T>) {
+        if value.is_none() {
+            self.free.entry(len).or_default().insert(start);
+        }
+        self.spans.insert(start, (len, value));
+    }
+
+    fn remove(&mut self, start: usize) -> (usize, Option<T>) {
+        let (len, value) = self.spans.remove(&start).expect("segment should exist");
+        if value.is_none()
+            && let Some(starts) = self.free.get_mut(&len)
+        {
+            starts.remove(&start);
+            if starts.is_empty() {
+                self.free.remove(&len);
+            }
+        }
+        (len, value)
+    }
+
+    /// Merges the free segment at `start` with free neighbours.
+    fn merge_free(&mut self, start: usize) {
+        let Some((len, None)) = self.spans.get(&start) else {
+            return;
+        };
+        let mut range = start..start + len;
+
+        let prev = self
+            .spans
+            .range(..start)
+            .next_back()
+            .filter(|(_, (_, value))| value.is_none())
+            .map(|(prev, _)| *prev);
+        let next_is_free = matches!(self.spans.get(&range.end), Some((_, None)));
+
+        if prev.is_none() && !next_is_free {
+            return;
+        }
+
+        self.remove(start);
+        if let Some(prev) = prev {
+            self.remove(prev);
+            range.start = prev;
+        }
+        if next_is_free {
+            range.end += self.remove(range.end).0;
+        }
+        self.insert(range.start, range.len(), None);
+    }
+}
+
+impl<T: Clone> Default for SegmentMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone> FromIterator<(usize, Option<T>)> for SegmentMap<T> {
+    /// Builds a map from consecutive `(length, value)` runs.
+    fn from_iter<I: IntoIterator<Item = (usize, Option<T>)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (len, value) in iter {
+            map.push(len, value);
+        }
+        map
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::SegmentMap;
+
+    /// `00...111...2...333.44.5555.6666.777.888899` from the 2024-09 example.
+    fn get_mock_disk() -> SegmentMap<u64> {
+        "2333133121414131402"
+            .bytes()
+            .enumerate()
+            .map(|(i, c)| {
+                let len = usize::from(c - b'0');
+                (len, if i % 2 == 0 { Some(i as u64 / 2) } else { None })
+            })
+            .collect()
+    }
+
+    fn render(disk: &SegmentMap<u64>) -> String {
+        disk.segments()
+            .map(|s| match s.value {
+                Some(id) => id.to_string().repeat(s.len),
+                None => ".".repeat(s.len),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn builds_from_runs() {
+        let disk = get_mock_disk();
+        assert_eq!(disk.len(), 42);
+        assert_eq!(render(&disk), "00...111...2...333.44.5555.6666.777.888899");
+        assert_eq!(disk.get(3).unwrap().len, 3);
+        assert_eq!(disk.get(42), None);
+    }
+
+    #[test]
+    fn finds_leftmost_fit() {
+        let disk = get_mock_disk();
+        assert_eq!(disk.leftmost_fit(1), Some((2, 3)));
+        assert_eq!(disk.leftmost_fit(3), Some((2, 3)));
+        assert_eq!(disk.leftmost_fit(4), None);
+    }
+
+    #[test]
+    fn merges_adjacent_free_segments() {
+        let mut disk = get_mock_disk();
+        disk.set(5, 3, None);
+        assert_eq!(render(&disk), "00.........2...333.44.5555.6666.777.888899");
+        assert_eq!(disk.leftmost_fit(4), Some((2, 9)));
+        assert_eq!(disk.segments().filter(|s| s.is_free()).count(), 7);
+    }
+
+    #[test]
+    fn splits_segments() {
+        let mut disk = get_mock_disk();
+        disk.set(3, 1, Some(9));
+        assert_eq!(render(&disk), "00.9.111...2...333.44.5555.6666.777.888899");
+        assert_eq!(disk.leftmost_fit(2), Some((8, 3)));
+        disk.split_at(6);
+        assert_eq!(disk.get(6).unwrap().start, 6);
+        assert_eq!(disk.get(5).unwrap().len, 1);
+    }
+
+    #[test]
+    fn computes_checksums() {
+        let disk: SegmentMap<u64> = [(2, Some(0)), (3, None), (3, Some(1))]
+            .into_iter()
+            .collect();
+        assert_eq!(disk.checksum(|id| *id), 5 + 6 + 7);
+        assert_eq!(
+            disk.blocks().map(|(pos, id)| pos as u64 * id).sum::<u64>(),
+            18
+        );
+    }
+}
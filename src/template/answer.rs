@@ -0,0 +1,197 @@
+/// Normalizes the values returned by solutions into comparable answers.
+use std::convert::Infallible;
+use std::fmt::Display;
+use std::iter::{Product, Sum};
+use std::str::FromStr;
+
+use crate::ocr;
+
+/// The answer to one part of a puzzle.
+///
+/// Numbers of every primitive integer type normalize to [`Answer::Number`], so days can return
+/// whatever type is convenient. Accumulating through [`Sum`] or [`Product`] is overflow-checked
+/// and panics in every build profile, instead of silently wrapping in `release` mode.
+///
+/// ```
+/// # use advent_of_code::template::answer::Answer;
+/// assert_eq!(Answer::from(42u32), Answer::from(42i64));
+/// assert_eq!([u32::MAX, 1].into_iter().sum::<Answer>(), Answer::from(1u64 << 32));
+/// assert!(Answer::from("ABC").matches(" ABC\n"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+impl Answer {
+    /// The text that is submitted for this answer.
+    ///
+    /// Multi-line text spelling out block letters is decoded with [`ocr::recognize`].
+    pub fn submission(&self) -> String {
+        match self {
+            Answer::Text(text) if text.contains('\n') => {
+                ocr::recognize(text).unwrap_or_else(|_| text.clone())
+            }
+            answer => answer.to_string(),
+        }
+    }
+
+    /// Returns whether this answer equals a recorded answer, ignoring surrounding whitespace.
+    pub fn matches(&self, recorded: &str) -> bool {
+        let recorded = recorded.trim();
+        match self {
+            Answer::Number(n) => recorded.parse() == Ok(*n),
+            Answer::Text(_) => self.submission().trim() == recorded,
+        }
+    }
+
+    /// Adds two numbers, returns [`None`] for text or on overflow.
+    pub fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        self.combine(rhs, i128::checked_add)
+    }
+
+    /// Multiplies two numbers, returns [`None`] for text or on overflow.
+    pub fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        self.combine(rhs, i128::checked_mul)
+    }
+
+    fn combine(&self, rhs: &Self, f: impl Fn(i128, i128) -> Option<i128>) -> Option<Self> {
+        match (self, rhs) {
+            (Answer::Number(a), Answer::Number(b)) => f(*a, *b).map(Answer::Number),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{n}"),
+            Answer::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    /// Parses a recorded answer: integers become [`Answer::Number`], anything else is text.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        Ok(s.parse()
+            .map_or_else(|_| Answer::Text(s.to_string()), Answer::Number))
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),* $(,)?) => {$(
+        impl From<$t> for Answer {
+            fn from(value: $t) -> Self {
+                Answer::Number(i128::from(value))
+            }
+        }
+    )*};
+}
+
+impl_from_int!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Self {
+        Answer::Number(value as i128)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(value: isize) -> Self {
+        Answer::Number(value as i128)
+    }
+}
+
+impl From<u128> for Answer {
+    /// Values beyond [`i128::MAX`] are kept as text.
+    fn from(value: u128) -> Self {
+        i128::try_from(value).map_or_else(|_| Answer::Text(value.to_string()), Answer::Number)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+impl From<char> for Answer {
+    fn from(value: char) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+impl<T: Into<Answer>> Sum<T> for Answer {
+    /// # Panics
+    /// Panics on overflow or if any item is text.
+    fn sum<I: Iterator<Item = T>>(iter: I) -> Self {
+        iter.fold(Answer::Number(0), |acc, item| {
+            acc.checked_add(&item.into())
+                .expect("sum of answers should be a number without overflow")
+        })
+    }
+}
+
+impl<T: Into<Answer>> Product<T> for Answer {
+    /// # Panics
+    /// Panics on overflow or if any item is text.
+    fn product<I: Iterator<Item = T>>(iter: I) -> Self {
+        iter.fold(Answer::Number(1), |acc, item| {
+            acc.checked_mul(&item.into())
+                .expect("product of answers should be a number without overflow")
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answer;
+
+    #[test]
+    fn normalizes_numbers() {
+        assert_eq!(Answer::from(7u8), Answer::from(7usize));
+        assert_eq!(Answer::from(-3i32), Answer::Number(-3));
+        assert_eq!(Answer::from(u128::MAX), Answer::Text(u128::MAX.to_string()));
+        assert_eq!("  42\n".parse(), Ok(Answer::Number(42)));
+        assert_eq!("abc".parse(), Ok(Answer::from("abc")));
+    }
+
+    #[test]
+    fn matches_recorded_answers() {
+        assert!(Answer::from(42u64).matches("42\n"));
+        assert!(!Answer::from(42u64).matches("43"));
+        assert!(Answer::from("1,2,3").matches("1,2,3"));
+
+        let art = "#..#\n#..#\n####\n#..#\n#..#\n#..#";
+        assert_eq!(Answer::from(art).submission(), "H");
+        assert!(Answer::from(art).matches("H"));
+    }
+
+    #[test]
+    fn accumulates_without_wrapping() {
+        let total: Answer = [u32::MAX, u32::MAX].into_iter().sum();
+        assert_eq!(total, Answer::from(2 * u64::from(u32::MAX)));
+        let product: Answer = [u64::MAX, 2].into_iter().product();
+        assert_eq!(product, Answer::from(u128::from(u64::MAX) * 2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_on_overflow() {
+        let _: Answer = [i128::MAX, 1].into_iter().sum();
+    }
+}
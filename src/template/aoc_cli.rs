@@ -1,11 +1,19 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
     fmt::Display,
+    fs,
+    path::Path,
     process::{Command, Output, Stdio},
 };
 
+use crate::template::config::{config, Profile};
+use crate::template::encryption::{self, Key};
+use crate::template::input::get_input_path;
 use crate::template::Day;
 
+/// The marker aoc-cli writes into a downloaded puzzle for every solved part.
+const ANSWER_MARKER: &str = "Your puzzle answer was";
+
 #[derive(Debug)]
 pub enum AocCommandError {
     CommandNotFound,
@@ -34,24 +42,35 @@ pub fn check() -> Result<(), AocCommandError> {
 }
 
 pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
+    let puzzle_path = get_puzzle_path(day, None);
 
     let args = build_args(
         "read",
         &[
             "--description-only".into(),
             "--puzzle-file".into(),
-            puzzle_path,
+            puzzle_path.clone(),
         ],
         day,
+        None,
     );
 
-    call_aoc_cli(&args)
+    let output = call_aoc_cli(&args)?;
+    encrypt_downloads(&[&puzzle_path]);
+    Ok(output)
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
+/// Downloads the input and puzzle of `day`, for the account of `profile` if given.
+pub fn download(day: Day, profile: Option<&Profile>) -> Result<Output, AocCommandError> {
+    let input_path = get_input_path(day, profile.map(|profile| profile.name.as_str()))
+        .display()
+        .to_string();
+    let puzzle_path = get_puzzle_path(day, profile);
+    for path in [&input_path, &puzzle_path] {
+        if let Some(dir) = Path::new(path).parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+    }
 
     let args = build_args(
         "download",
@@ -63,34 +82,82 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
             puzzle_path.to_string(),
         ],
         day,
+        profile,
     );
 
     let output = call_aoc_cli(&args)?;
     println!("---");
     println!("🎄 Successfully wrote input to \"{}\".", &input_path);
     println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    encrypt_downloads(&[&input_path, &puzzle_path]);
     Ok(output)
 }
 
 pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
     // workaround: the argument order is inverted for submit.
-    let mut args = build_args("submit", &[], day);
+    let mut args = build_args("submit", &[], day, None);
     args.push(part.to_string());
     args.push(result.to_string());
     call_aoc_cli(&args)
 }
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+/// Encrypts downloaded files if a key for inputs is set, see [`encryption`].
+fn encrypt_downloads(paths: &[&str]) {
+    let key = match Key::read() {
+        Ok(Some(key)) => key,
+        Ok(None) => return,
+        Err(e) => {
+            eprintln!("Could not encrypt the downloaded files: {e}");
+            return;
+        }
+    };
+
+    for path in paths {
+        match encryption::lock(&key, Path::new(path)) {
+            Ok(true) => println!("🔒 Encrypted \"{path}\"."),
+            Ok(false) => {}
+            Err(e) => eprintln!("Could not encrypt \"{path}\": {e}"),
+        }
+    }
+}
+
+/// The answers accepted for the parts of a puzzle, as recorded in the downloaded puzzle.
+pub fn recorded_answers(puzzle: &str) -> Vec<String> {
+    puzzle
+        .match_indices(ANSWER_MARKER)
+        .filter_map(|(index, _)| {
+            let rest = puzzle[index + ANSWER_MARKER.len()..].trim_start();
+            let answer = match rest.strip_prefix('`') {
+                Some(rest) => rest.split('`').next()?,
+                None => rest.split(['.', '\n']).next()?,
+            };
+            Some(answer.trim().to_string())
+        })
+        .collect()
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+fn get_puzzle_path(day: Day, profile: Option<&Profile>) -> String {
+    match profile {
+        Some(profile) => config().profile_day_file("puzzles", &profile.name, day, ".md"),
+        None => config().day_file("puzzles", day, ".md"),
+    }
+    .display()
+    .to_string()
 }
 
-fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
+fn build_args(command: &str, args: &[String], day: Day, profile: Option<&Profile>) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
+    // a profile never falls back to the cookie of the main account.
+    let session_file = match profile {
+        Some(profile) => Some(&profile.session_file),
+        None => config().session_file.as_ref(),
+    };
+    if let Some(session_file) = session_file {
+        cmd_args.push("--session-file".into());
+        cmd_args.push(session_file.display().to_string());
+    }
+
     cmd_args.append(&mut vec![
         "--year".into(),
         day.into_inner().0.to_string(),
@@ -117,3 +184,18 @@ fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
         Err(AocCommandError::BadExitStatus(output))
     }
 }
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::recorded_answers;
+
+    #[test]
+    fn reads_recorded_answers() {
+        let puzzle = "--- Day 5: Print Queue ---\n...\nYour puzzle answer was `143`.\n\n\
+            --- Part Two ---\n...\nYour puzzle answer was 123.\n";
+        assert_eq!(recorded_answers(puzzle), ["143", "123"]);
+        assert!(recorded_answers("--- Day 5: Print Queue ---").is_empty());
+    }
+}
@@ -1,5 +1,256 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::aoc_cli::recorded_answers;
+use crate::template::config::config;
+use crate::template::input::get_input_path;
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::{all_days, encryption, run_multi::run_multi, Day};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// The column of the main account's inputs.
+const DEFAULT_PROFILE: &str = "default";
+
+const LEGEND: [(char, &str); 6] = [
+    ('✓', "matches the recorded answer"),
+    ('✗', "differs from the recorded answer"),
+    ('?', "no recorded answer"),
+    ('·', "no answer"),
+    ('!', "solution failed"),
+    ('-', "no input"),
+];
+
+pub fn handle(is_release: bool, profiles: bool) {
+    if profiles {
+        if !run_profiles(is_release) {
+            process::exit(1);
+        }
+    } else {
+        run_multi(&all_days().collect(), is_release, false, None);
+    }
+}
+
+/// The result of running a solution against one input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Run {
+    NoInput,
+    Failed(String),
+    Solved([Part; 2]),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Part {
+    Matches,
+    Differs { expected: String, actual: String },
+    Unverified,
+    NoAnswer,
+}
+
+impl Run {
+    fn symbols(&self) -> String {
+        match self {
+            Run::NoInput => "-".into(),
+            Run::Failed(_) => "!".into(),
+            Run::Solved(parts) => parts
+                .iter()
+                .map(|part| match part {
+                    Part::Matches => '✓',
+                    Part::Differs { .. } => '✗',
+                    Part::Unverified => '?',
+                    Part::NoAnswer => '·',
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Runs every solution against the inputs of the main account and of every profile, and
+/// compares the answers with the ones recorded in the downloaded puzzles. Returns whether all
+/// recorded answers match.
+fn run_profiles(is_release: bool) -> bool {
+    let mut columns = vec![DEFAULT_PROFILE];
+    columns.extend(
+        config()
+            .profiles
+            .iter()
+            .map(|profile| profile.name.as_str()),
+    );
+
+    let days: Vec<Day> = all_days()
+        .filter(|day| Path::new(&get_path_for_bin(*day)).exists())
+        .collect();
+
+    println!("{ANSI_ITALIC}Running solutions...{ANSI_RESET}");
+    let rows: Vec<(Day, Vec<Run>)> = days
+        .iter()
+        .map(|day| {
+            let runs: Vec<Run> = columns
+                .iter()
+                .map(|profile| run_profile(*day, profile, is_release))
+                .collect();
+            (*day, runs)
+        })
+        .filter(|(_, runs)| runs.iter().any(|run| *run != Run::NoInput))
+        .collect();
+
+    if rows.is_empty() {
+        println!("No inputs yet. Try running \"cargo download <day> --profile <name>\" first.");
+        return true;
+    }
+
+    print_matrix(&columns, &rows);
+    print_failures(&columns, &rows)
+}
+
+fn run_profile(day: Day, profile: &str, is_release: bool) -> Run {
+    let (input, puzzle) = profile_paths(day, profile);
+    if !encryption::exists(&input) {
+        return Run::NoInput;
+    }
+
+    let day_str = day.to_string();
+    let input_str = input.display().to_string();
+    let mut args = vec!["run", "--quiet", "--bin", &day_str];
+    if is_release {
+        args.push("--release");
+    }
+    args.extend(["--", "--input", &input_str]);
+
+    let output = match Command::new("cargo").args(&args).output() {
+        Ok(output) => output,
+        Err(e) => return Run::Failed(e.to_string()),
+    };
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        // skip the backtrace hint that follows a panic message.
+        let reason = stderr
+            .lines()
+            .rfind(|line| !line.trim().is_empty() && !line.starts_with("note:"));
+        return Run::Failed(reason.unwrap_or("exited with an error").trim().to_string());
+    }
+
+    let answers = parse_answers(&String::from_utf8_lossy(&output.stdout));
+    let recorded = encryption::read_to_string(&puzzle)
+        .map(|puzzle| recorded_answers(&puzzle))
+        .unwrap_or_default();
+
+    Run::Solved([0, 1].map(|i| match (&answers[i], recorded.get(i)) {
+        (None, _) => Part::NoAnswer,
+        (Some(_), None) => Part::Unverified,
+        (Some(actual), Some(expected)) if actual == expected => Part::Matches,
+        (Some(actual), Some(expected)) => Part::Differs {
+            expected: expected.clone(),
+            actual: actual.clone(),
+        },
+    }))
+}
+
+/// The input and puzzle of `day` for `profile`.
+fn profile_paths(day: Day, profile: &str) -> (PathBuf, PathBuf) {
+    if profile == DEFAULT_PROFILE {
+        (
+            get_input_path(day, None),
+            config().day_file("puzzles", day, ".md"),
+        )
+    } else {
+        (
+            get_input_path(day, Some(profile)),
+            config().profile_day_file("puzzles", profile, day, ".md"),
+        )
+    }
+}
+
+/// Parses the answers from the output of a solution, whose lines read `Part 1: <answer> (...)`.
+fn parse_answers(stdout: &str) -> [Option<String>; 2] {
+    let mut answers = [None, None];
+    for line in stdout.lines() {
+        // the runner overwrites the intermediate result with a carriage return.
+        let line = line.rsplit('\r').next().unwrap_or(line);
+        for (part, answer) in answers.iter_mut().enumerate() {
+            let Some(result) = line.strip_prefix(&format!("Part {}: ", part + 1)) else {
+                continue;
+            };
+            *answer = result
+                .strip_prefix(ANSI_BOLD)
+                .and_then(|result| result.split(ANSI_RESET).next())
+                .map(ToString::to_string);
+        }
+    }
+    answers
+}
+
+fn print_matrix(columns: &[&str], rows: &[(Day, Vec<Run>)]) {
+    let widths: Vec<usize> = columns.iter().map(|name| name.len().max(2) + 2).collect();
+
+    println!();
+    let header: String = columns
+        .iter()
+        .zip(&widths)
+        .map(|(name, width)| format!("{name:<width$}"))
+        .collect();
+    println!("{ANSI_BOLD}{:<10}{}{ANSI_RESET}", "Day", header.trim_end());
+
+    for (day, runs) in rows {
+        let line: String = runs
+            .iter()
+            .zip(&widths)
+            .map(|(run, width)| format!("{:<width$}", run.symbols()))
+            .collect();
+        println!("{:<10}{}", day.to_string(), line.trim_end());
+    }
+
+    println!();
+    for (symbol, name) in LEGEND {
+        println!("{symbol} {name}");
+    }
+}
+
+/// Lists every differing answer and failed solution. Returns whether there were none.
+fn print_failures(columns: &[&str], rows: &[(Day, Vec<Run>)]) -> bool {
+    let mut failures = vec![];
+    for (day, runs) in rows {
+        for (profile, run) in columns.iter().zip(runs) {
+            match run {
+                Run::Failed(reason) => failures.push(format!("{day} ({profile}): {reason}")),
+                Run::Solved(parts) => {
+                    for (i, part) in parts.iter().enumerate() {
+                        if let Part::Differs { expected, actual } = part {
+                            failures.push(format!(
+                                "{day} ({profile}), part {}: expected {expected}, got {actual}",
+                                i + 1
+                            ));
+                        }
+                    }
+                }
+                Run::NoInput => {}
+            }
+        }
+    }
+
+    if !failures.is_empty() {
+        println!();
+        println!("{ANSI_BOLD}Mismatches{ANSI_RESET}");
+        for failure in &failures {
+            println!("{failure}");
+        }
+    }
+    failures.is_empty()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::parse_answers;
+    use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+    #[test]
+    fn parses_answers() {
+        let stdout = format!(
+            "Part 1: {ANSI_BOLD}11{ANSI_RESET}\rPart 1: {ANSI_BOLD}11{ANSI_RESET} (11.7µs)\n\
+            Part 2: ✖\rPart 2: ✖             \n"
+        );
+        assert_eq!(parse_answers(&stdout), [Some("11".to_string()), None]);
+        assert_eq!(parse_answers(""), [None, None]);
+    }
 }
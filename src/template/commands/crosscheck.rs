@@ -0,0 +1,42 @@
+use std::path::Path;
+use std::process::{self, Command, Stdio};
+
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::Day;
+
+pub fn handle(day: Day, release: bool, iterations: u64, seed: Option<u64>, part: Option<u8>) {
+    if !Path::new(&get_path_for_bin(day)).exists() {
+        eprintln!("Solution for {day} does not exist. Try running \"cargo scaffold {day}\" first.");
+        process::exit(1);
+    }
+
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+    if release {
+        cmd_args.push("--release".to_string());
+    }
+
+    cmd_args.push("--".to_string());
+    cmd_args.push("--crosscheck".to_string());
+    cmd_args.push(iterations.to_string());
+
+    if let Some(seed) = seed {
+        cmd_args.push("--seed".to_string());
+        cmd_args.push(seed.to_string());
+    }
+
+    if let Some(part) = part {
+        cmd_args.push("--part".to_string());
+        cmd_args.push(part.to_string());
+    }
+
+    let status = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status()
+        .unwrap();
+
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
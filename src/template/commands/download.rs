@@ -1,14 +1,42 @@
+use crate::template::config::{config, Profile};
 use crate::template::{aoc_cli, Day};
 use std::process;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+/// Downloads the input and puzzle of `day`, for the account of `profile` if given.
+pub fn handle(day: Day, profile: Option<&str>) {
+    let profile = profile.map(|name| match config().profile(name) {
+        Some(profile) => profile,
+        None => {
+            let names: Vec<&str> = config().profiles.iter().map(|p| p.name.as_str()).collect();
+            eprintln!(
+                "Profile \"{name}\" is not configured in \"aoc.toml\". Available profiles: {}",
+                names.join(", ")
+            );
+            process::exit(1);
+        }
+    });
+
+    if let Err(e) = download(day, profile) {
+        eprintln!("{e}");
         process::exit(1);
     }
+}
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+/// Downloads the input and puzzle of `day` before scaffolding it, so that the template can use
+/// the puzzle title. A failed download only warns, the day is scaffolded regardless.
+pub fn handle_before_scaffold(day: Day) {
+    if let Err(e) = download(day, None) {
+        eprintln!("{e}");
+        eprintln!("Scaffolding {day} without the puzzle.");
+    }
+}
+
+fn download(day: Day, profile: Option<&Profile>) -> Result<(), String> {
+    if aoc_cli::check().is_err() {
+        return Err("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.".into());
+    }
+
+    aoc_cli::download(day, profile)
+        .map(|_| ())
+        .map_err(|e| format!("failed to call aoc-cli: {e}"))
 }
@@ -0,0 +1,62 @@
+use std::path::PathBuf;
+use std::process;
+
+use crate::template::config::config;
+use crate::template::encryption::{self, encrypted_path, Key};
+use crate::template::{all_days, ANSI_BOLD, ANSI_RESET};
+
+/// Encrypts all inputs and puzzles with `lock`, decrypts them otherwise.
+pub fn handle(lock: bool) {
+    let key = match Key::require() {
+        Ok(key) => key,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let mut converted = 0;
+    for path in data_files() {
+        let result = if lock {
+            encryption::lock(&key, &path)
+        } else {
+            encryption::unlock(&key, &path)
+        };
+
+        match result {
+            Ok(true) if lock => println!("🔒 Encrypted \"{}\"", path.display()),
+            Ok(true) => println!("🔓 Decrypted \"{}\"", encrypted_path(&path).display()),
+            Ok(false) => continue,
+            Err(e) => {
+                eprintln!(
+                    "{ANSI_BOLD}Failed to convert \"{}\"{ANSI_RESET}: {e}",
+                    path.display()
+                );
+                process::exit(1);
+            }
+        }
+        converted += 1;
+    }
+
+    println!("---");
+    if lock {
+        println!("🎄 Encrypted {converted} file(s). The plaintext files were removed.");
+    } else {
+        println!("🎄 Decrypted {converted} file(s). Run `cargo inputs lock` before committing.");
+    }
+}
+
+/// The plaintext paths of the inputs and puzzles of every day, including those of profiles.
+fn data_files() -> impl Iterator<Item = PathBuf> {
+    all_days().flat_map(|day| {
+        let mut files = vec![
+            config().day_file("inputs", day, ".txt"),
+            config().day_file("puzzles", day, ".md"),
+        ];
+        for profile in &config().profiles {
+            files.push(config().profile_day_file("inputs", &profile.name, day, ".txt"));
+            files.push(config().profile_day_file("puzzles", &profile.name, day, ".md"));
+        }
+        files
+    })
+}
@@ -0,0 +1,160 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use crate::template::config::{config, day_stem, layout_bin_path, write_layout, Layout};
+use crate::template::{all_days, manifest, Day};
+
+/// The data folders whose files are named after days.
+const DAY_FOLDERS: [&str; 3] = ["inputs", "examples", "puzzles"];
+
+pub fn handle(target: Layout) {
+    let source = match target {
+        Layout::Flat => Layout::PerYear,
+        Layout::PerYear => Layout::Flat,
+    };
+
+    let mut moved = 0;
+    for day in all_days() {
+        match migrate_day(day, source, target) {
+            Ok(n) => moved += n,
+            Err(e) => {
+                eprintln!("Failed to migrate {day}: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    if let Err(e) = write_layout(target) {
+        eprintln!("Failed to update \"aoc.toml\": {e}");
+        process::exit(1);
+    }
+
+    println!("---");
+    println!("🎄 Moved {moved} file(s) to the {target} layout.");
+}
+
+/// Moves the solution and data files of `day`. Returns the number of moved files.
+fn migrate_day(day: Day, source: Layout, target: Layout) -> Result<usize, io::Error> {
+    let mut moved = 0;
+
+    let bin = layout_bin_path(source, day);
+    if bin.exists() {
+        if move_file(&bin, &layout_bin_path(target, day))? {
+            moved += 1;
+        }
+        match target {
+            Layout::PerYear => manifest::register_bin(day, &layout_bin_path(target, day))?,
+            Layout::Flat => manifest::unregister_bin(day)?,
+        };
+        remove_if_empty(bin.parent());
+    }
+
+    // the files of profiles live in subfolders, e.g. `data/inputs/alice`.
+    let profiles = config()
+        .profiles
+        .iter()
+        .map(|profile| profile.name.as_str());
+    let subfolders: Vec<&str> = [""].into_iter().chain(profiles).collect();
+
+    for (folder, subfolder) in DAY_FOLDERS.iter().flat_map(|folder| {
+        subfolders
+            .iter()
+            .map(move |subfolder| (*folder, *subfolder))
+    }) {
+        let source_dir = config().layout_day_dir(source, folder, day).join(subfolder);
+        let target_dir = config().layout_day_dir(target, folder, day).join(subfolder);
+        let (source_stem, target_stem) = (day_stem(source, day), day_stem(target, day));
+
+        for path in day_files(&source_dir, &source_stem)? {
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            let renamed = format!("{target_stem}{}", &name[source_stem.len()..]);
+            let destination = target_dir.join(renamed);
+
+            if folder == "examples" && name.ends_with(".toml") {
+                rename_example_files(&path, &source_stem, &target_stem)?;
+            }
+            if move_file(&path, &destination)? {
+                moved += 1;
+            }
+        }
+        // e.g. `data/2024/inputs/alice`, `data/2024/inputs` and `data/2024`.
+        for dir in source_dir.ancestors().take(3) {
+            remove_if_empty(Some(dir));
+        }
+    }
+
+    Ok(moved)
+}
+
+/// The files in `dir` that belong to the day named `stem`, e.g. `05.txt` or `05-2.txt`.
+fn day_files(dir: &Path, stem: &str) -> Result<Vec<PathBuf>, io::Error> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e),
+    };
+
+    let mut files = vec![];
+    for entry in entries {
+        let path = entry?.path();
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if let Some(rest) = name.strip_prefix(stem)
+            && (rest.starts_with('.') || rest.starts_with('-'))
+            && path.is_file()
+        {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+/// Moves `from` to `to`. Returns `false` and keeps `from` if `to` already exists.
+fn move_file(from: &Path, to: &Path) -> Result<bool, io::Error> {
+    if to.exists() {
+        eprintln!(
+            "Skipped \"{}\", \"{}\" already exists",
+            from.display(),
+            to.display()
+        );
+        return Ok(false);
+    }
+    if let Some(dir) = to.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::rename(from, to)?;
+    println!("Moved \"{}\" to \"{}\"", from.display(), to.display());
+    Ok(true)
+}
+
+/// Renames the example files referenced by the `file` keys of a metadata file.
+fn rename_example_files(
+    metadata: &Path,
+    source_stem: &str,
+    target_stem: &str,
+) -> Result<(), io::Error> {
+    let contents = fs::read_to_string(metadata)?;
+    let renamed: Vec<String> = contents
+        .lines()
+        .map(|line| {
+            let is_file_key = line
+                .split('=')
+                .next()
+                .is_some_and(|key| key.trim() == "file");
+            if is_file_key {
+                line.replacen(&format!("\"{source_stem}"), &format!("\"{target_stem}"), 1)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    fs::write(metadata, renamed.join("\n") + "\n")
+}
+
+fn remove_if_empty(dir: Option<&Path>) {
+    // only succeeds for empty directories.
+    if let Some(dir) = dir {
+        let _ = fs::remove_dir(dir);
+    }
+}
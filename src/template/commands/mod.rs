@@ -1,6 +1,12 @@
 pub mod all;
+pub mod crosscheck;
 pub mod download;
+pub mod inputs;
+pub mod migrate_layout;
+pub mod new_year;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod status;
 pub mod time;
+pub mod watch_day;
@@ -0,0 +1,51 @@
+use std::path::Path;
+use std::process;
+
+use crate::template::commands::scaffold;
+use crate::template::config::{config, write_year};
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::{days_in_event, readme_benchmarks, server_date, Day, FIRST_YEAR};
+
+pub fn handle(year: u16, scaffold_days: bool) {
+    // the event of the current year can be set up before it starts.
+    let (current_year, _, _) = server_date();
+    if year < FIRST_YEAR || i64::from(year) > current_year {
+        eprintln!(
+            "Expecting the year of an event from {FIRST_YEAR} to {current_year}, found {year}."
+        );
+        process::exit(1);
+    }
+
+    match write_year(year) {
+        Ok(()) => println!("Set the default year to {year} in \"aoc.toml\""),
+        Err(e) => {
+            eprintln!("Failed to update \"aoc.toml\": {e}");
+            process::exit(1);
+        }
+    }
+
+    let readme = config().readme.display();
+    match readme_benchmarks::add_year_table(year) {
+        Ok(true) => println!("Added the {year} benchmarks to \"{readme}\""),
+        Ok(false) => println!("\"{readme}\" already has the {year} benchmarks"),
+        Err(e) => {
+            eprintln!("Failed to update \"{readme}\": {e:?}");
+            process::exit(1);
+        }
+    }
+
+    if scaffold_days {
+        scaffold::offer_year_module(year, false);
+        for day in (1..=days_in_event(year)).filter_map(|day| Day::new(year, day)) {
+            println!();
+            if Path::new(&get_path_for_bin(day)).exists() {
+                println!("Skipped {day}, it is already scaffolded");
+            } else {
+                scaffold::handle(day, false, None);
+            }
+        }
+    } else {
+        println!("---");
+        println!("🎄 Type `cargo scaffold 1` to start with the first day.");
+    }
+}
@@ -1,15 +1,35 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{self, IsTerminal, Write},
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::config::{config, Layout};
+use crate::template::examples::get_metadata_path;
+use crate::template::{all_days, encryption, manifest, years};
+use crate::template::{server_date, Day};
 
+/// The template shipped with the crate, used if the configured template does not exist.
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
+const EXAMPLE_METADATA_TEMPLATE: &str = "\
+# Expected answers for the example input. Add more `[[example]]` tables for further examples.
+[[example]]
+# part_one = 0
+# part_two = 0
+";
+
+fn create_parent_dir(path: &str) -> Result<(), std::io::Error> {
+    match Path::new(path).parent() {
+        Some(dir) => fs::create_dir_all(dir),
+        None => Ok(()),
+    }
+}
+
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
+    create_parent_dir(path)?;
     let mut file = OpenOptions::new();
     if overwrite {
         file.create(true);
@@ -19,18 +39,153 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// Reads the template `name` from the templates directory. Without a name, reads the configured
+/// module template, falling back to the built-in one.
+fn read_module_template(name: Option<&str>) -> Result<String, String> {
+    let Some(name) = name else {
+        let path = &config().template;
+        return match fs::read_to_string(path) {
+            Ok(template) => Ok(template),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(MODULE_TEMPLATE.to_string()),
+            Err(e) => Err(format!(
+                "Failed to read module template \"{}\": {e}",
+                path.display()
+            )),
+        };
+    };
+
+    let path = config().templates_dir.join(format!("{name}.txt"));
+    match fs::read_to_string(&path) {
+        Ok(template) => Ok(template),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(format!(
+            "Template \"{name}\" does not exist in \"{}\". Available templates: {}",
+            config().templates_dir.display(),
+            available_templates().join(", ")
+        )),
+        Err(e) => Err(format!(
+            "Failed to read module template \"{}\": {e}",
+            path.display()
+        )),
+    }
+}
+
+/// The names of the templates in the templates directory.
+fn available_templates() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(&config().templates_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .filter_map(|path| Some(path.file_stem()?.to_string_lossy().into_owned()))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Substitutes the placeholders of a module template:
+///
+/// - `%DAY_NUMBER%`: the arguments of `solution!`, e.g. `2024, 05`.
+/// - `%YEAR%` and `%DAY%`: e.g. `2024` and `05`.
+/// - `%TITLE%`: the puzzle title if the puzzle was downloaded, `Day 5` otherwise.
+/// - `%DATE%`: today's date in the timezone of the puzzle release, e.g. `2024-12-05`.
+fn render_template(template: &str, day: Day) -> String {
+    let (year, day_number) = day.into_inner();
+    let title = read_puzzle_title(day).unwrap_or_else(|| format!("Day {day_number}"));
+
+    template
+        .replace("%DAY_NUMBER%", &day.to_string().replace('-', ", "))
+        .replace("%YEAR%", &year.to_string())
+        .replace("%DAY%", &format!("{day_number:02}"))
+        .replace("%TITLE%", &title)
+        .replace("%DATE%", &today())
+}
+
+/// Reads the title from a downloaded puzzle, whose heading reads `--- Day 5: Print Queue ---`.
+fn read_puzzle_title(day: Day) -> Option<String> {
+    let path = config().day_file("puzzles", day, ".md");
+    let puzzle = encryption::read_to_string(&path).ok()?;
+    let heading = puzzle.lines().find(|line| line.contains("--- Day "))?;
+    let (_, title) = heading.split_once(": ")?;
+    Some(title.trim_end_matches('-').trim().to_string())
+}
+
+/// Today's date as `YYYY-MM-DD`, in the timezone of the puzzle releases.
+fn today() -> String {
+    let (year, month, day) = server_date();
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Creates an empty file, keeping the contents of an existing one (e.g. a downloaded input).
+/// Returns whether the file was created. Nothing is created if only the encrypted counterpart
+/// exists, as an empty plaintext file would take precedence over it.
+fn create_file(path: &str) -> Result<bool, std::io::Error> {
+    if encryption::exists(Path::new(path)) {
+        return Ok(false);
+    }
+    create_parent_dir(path)?;
+    match OpenOptions::new().write(true).create_new(true).open(path) {
+        Ok(_) => Ok(true),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(e),
+    }
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Creates the module shared by the solutions of `year` if `create` is set. Otherwise, offers to
+/// create it when scaffolding the first day of the year in a terminal.
+pub fn offer_year_module(year: u16, create: bool) {
+    let path = years::module_path(year);
+    if path.exists() {
+        return;
+    }
+
+    if !create {
+        let first_day = !all_days()
+            .filter(|day| day.into_inner().0 == year)
+            .any(|day| config().bin_path(day).exists());
+        if !first_day || !io::stdin().is_terminal() {
+            return;
+        }
+
+        print!(
+            "Create \"{}\" for code shared by the solutions of {year}? [y/N] ",
+            path.display()
+        );
+        let mut answer = String::new();
+        if io::stdout()
+            .flush()
+            .and_then(|()| io::stdin().read_line(&mut answer))
+            .is_err()
+            || !answer.trim().eq_ignore_ascii_case("y")
+        {
+            return;
+        }
+    }
+
+    match years::create(year) {
+        Ok(_) => println!("Created year module \"{}\"", path.display()),
+        Err(e) => {
+            eprintln!("Failed to create year module: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+pub fn handle(day: Day, overwrite: bool, template: Option<&str>) {
+    let input_path = config().day_file("inputs", day, ".txt");
+    let input_path = input_path.to_string_lossy();
+    let example_path = config().day_file("examples", day, ".txt");
+    let example_path = example_path.to_string_lossy();
+    let module_path = config().bin_path(day);
+    let module_path = module_path.to_string_lossy();
+
+    let template = match read_module_template(template) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,11 +195,13 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.to_string().replace('-', ", "))
-            .as_bytes(),
-    ) {
+    let mut module = render_template(&template, day);
+    let (year, _) = day.into_inner();
+    if years::module_path(year).exists() {
+        module = years::with_import(&module, year);
+    }
+
+    match file.write_all(module.as_bytes()) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
         }
@@ -54,10 +211,23 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
+    // cargo only discovers solutions in `src/bin/*.rs` by itself.
+    if config().layout == Layout::PerYear {
+        match manifest::register_bin(day, Path::new(module_path.as_ref())) {
+            Ok(true) => println!("Registered binary \"{day}\" in \"Cargo.toml\""),
+            Ok(false) => {}
+            Err(e) => {
+                eprintln!("Failed to register binary in \"Cargo.toml\": {e}");
+                process::exit(1);
+            }
+        }
+    }
+
     match create_file(&input_path) {
-        Ok(_) => {
+        Ok(true) => {
             println!("Created empty input file \"{}\"", &input_path);
         }
+        Ok(false) => {}
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
             process::exit(1);
@@ -65,15 +235,63 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     match create_file(&example_path) {
-        Ok(_) => {
+        Ok(true) => {
             println!("Created empty example file \"{}\"", &example_path);
         }
+        Ok(false) => {}
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
             process::exit(1);
         }
     }
 
+    let metadata_path = get_metadata_path(day);
+    // never overwritten, as it holds the recorded answers of the examples.
+    match safe_create_file(&metadata_path.to_string_lossy(), false)
+        .and_then(|mut file| file.write_all(EXAMPLE_METADATA_TEMPLATE.as_bytes()))
+    {
+        Ok(()) => {
+            println!(
+                "Created example metadata file \"{}\"",
+                &metadata_path.display()
+            );
+        }
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+        Err(e) => {
+            eprintln!("Failed to create example metadata file: {e}");
+            process::exit(1);
+        }
+    }
+
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
 }
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs};
+
+    use super::create_file;
+    use crate::template::encryption::encrypted_path;
+
+    #[test]
+    fn keeps_encrypted_inputs() {
+        let dir = env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let path = dir.join("2024-05.txt");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(encrypted_path(&path), b"encrypted").unwrap();
+
+        assert!(!create_file(&path.to_string_lossy()).unwrap());
+        assert!(!path.exists());
+
+        fs::remove_file(encrypted_path(&path)).unwrap();
+        assert!(create_file(&path.to_string_lossy()).unwrap());
+        assert!(!create_file(&path.to_string_lossy()).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
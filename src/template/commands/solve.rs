@@ -1,8 +1,21 @@
 use std::process::{Command, Stdio};
 
+use crate::template::input::InputSource;
+use crate::template::playback::Playback;
 use crate::template::Day;
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    part: Option<u8>,
+    variants: bool,
+    params: &[String],
+    input: &InputSource,
+    playback: Option<&Playback>,
+) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -16,6 +29,11 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push("--release".to_string());
     }
 
+    if playback.is_some() {
+        cmd_args.push("--features".to_string());
+        cmd_args.push("visualize".to_string());
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -23,6 +41,26 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    if let Some(part) = part {
+        cmd_args.push("--part".to_string());
+        cmd_args.push(part.to_string());
+    }
+
+    if variants {
+        cmd_args.push("--variants".to_string());
+    }
+
+    cmd_args.extend(input.to_args());
+
+    for param in params {
+        cmd_args.push("--param".to_string());
+        cmd_args.push(param.clone());
+    }
+
+    if let Some(playback) = playback {
+        cmd_args.extend(playback.to_args());
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -0,0 +1,324 @@
+/// Summarizes the progress of every day of a year, from scaffolded to benchmarked.
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::Path;
+use std::process::{self, Command, Stdio};
+use std::str::FromStr;
+
+use tinyjson::JsonValue;
+
+use crate::template::aoc_cli::recorded_answers;
+use crate::template::config::config;
+use crate::template::examples::try_read_examples;
+use crate::template::input::get_input_path;
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::timings::Timings;
+use crate::template::{
+    all_days, days_in_event, encryption, latest_event_year, Day, ANSI_BOLD, ANSI_ITALIC,
+    ANSI_RESET, FIRST_YEAR,
+};
+
+const LEGEND: [(char, &str); 8] = [
+    ('·', "not started"),
+    ('○', "scaffolded"),
+    ('◔', "input downloaded"),
+    ('◑', "example answers recorded"),
+    ('◕', "tests pass"),
+    ('●', "answers verified"),
+    ('★', "verified and benchmarked"),
+    ('✖', "tests fail"),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Tests {
+    Passed,
+    Failed,
+    NotRun,
+}
+
+#[derive(Clone, Debug)]
+struct DayStatus {
+    day: Day,
+    scaffolded: bool,
+    input: bool,
+    /// Whether the example metadata records at least one answer.
+    examples: bool,
+    tests: Tests,
+    /// The number of parts whose answer was accepted, according to the downloaded puzzle.
+    verified: usize,
+    benchmarked: bool,
+}
+
+impl DayStatus {
+    /// The icon of the most advanced state, see [`LEGEND`].
+    fn icon(&self) -> char {
+        if !self.scaffolded {
+            '·'
+        } else if self.tests == Tests::Failed {
+            '✖'
+        } else if self.verified >= answers(self.day) && self.benchmarked {
+            '★'
+        } else if self.verified >= answers(self.day) {
+            '●'
+        } else if self.examples && self.tests == Tests::Passed {
+            '◕'
+        } else if self.examples {
+            '◑'
+        } else if self.input {
+            '◔'
+        } else {
+            '○'
+        }
+    }
+}
+
+/// The number of answers of `day`. The last day of an event only has one puzzle.
+fn answers(day: Day) -> usize {
+    let (year, day) = day.into_inner();
+    if day == days_in_event(year) {
+        1
+    } else {
+        2
+    }
+}
+
+pub fn handle(year: Option<u16>, json: bool) {
+    if let Some(year) = year
+        && !all_days().any(|day| day.into_inner().0 == year)
+    {
+        eprintln!(
+            "Expecting the year of an event from {FIRST_YEAR} to {}, found {year}.",
+            latest_event_year()
+        );
+        process::exit(1);
+    }
+
+    let years: BTreeSet<u16> = match year.or(config().year) {
+        Some(year) => BTreeSet::from([year]),
+        // without a default year, show every year with a solution.
+        None => all_days()
+            .filter(|day| is_scaffolded(*day))
+            .map(|day| day.into_inner().0)
+            .collect(),
+    };
+
+    let days: Vec<Day> = all_days()
+        .filter(|day| years.contains(&day.into_inner().0))
+        .collect();
+
+    if !json {
+        println!("{ANSI_ITALIC}Running tests...{ANSI_RESET}");
+    }
+    let scaffolded = days.iter().copied().filter(|day| is_scaffolded(*day));
+    let tests = run_tests(&scaffolded.collect());
+    let timings = Timings::read_from_file();
+
+    let statuses: Vec<DayStatus> = days
+        .into_iter()
+        .map(|day| read_status(day, &tests, &timings))
+        .collect();
+
+    if json {
+        print_json(&years, &statuses);
+    } else {
+        print_calendar(&years, &statuses);
+    }
+}
+
+fn is_scaffolded(day: Day) -> bool {
+    Path::new(&get_path_for_bin(day)).exists()
+}
+
+fn read_status(day: Day, tests: &Option<HashMap<Day, bool>>, timings: &Timings) -> DayStatus {
+    let scaffolded = is_scaffolded(day);
+    let examples = try_read_examples(day).is_ok_and(|examples| {
+        examples
+            .iter()
+            .any(|example| example.part_one.is_some() || example.part_two.is_some())
+    });
+    let tests = match tests {
+        Some(tests) if scaffolded => match tests.get(&day) {
+            Some(true) => Tests::Passed,
+            // no test binary means that the solution did not compile.
+            Some(false) | None => Tests::Failed,
+        },
+        _ => Tests::NotRun,
+    };
+    let puzzle_path = config().day_file("puzzles", day, ".md");
+    let verified = encryption::read_to_string(&puzzle_path)
+        .map(|puzzle| recorded_answers(&puzzle).len())
+        .unwrap_or(0);
+
+    DayStatus {
+        day,
+        scaffolded,
+        input: encryption::exists(&get_input_path(day, None)),
+        examples,
+        tests,
+        verified,
+        benchmarked: timings.is_day_complete(day),
+    }
+}
+
+/// Builds the tests of all solutions and runs those of `days`. Returns whether each day's
+/// tests pass, or [`None`] if cargo could not be called.
+fn run_tests(days: &HashSet<Day>) -> Option<HashMap<Day, bool>> {
+    if days.is_empty() {
+        return Some(HashMap::new());
+    }
+
+    // `cargo test` stops at the first solution that fails to compile, `cargo build` keeps going.
+    let output = Command::new("cargo")
+        .args([
+            "build",
+            "--quiet",
+            "--tests",
+            "--keep-going",
+            "--message-format=json",
+        ])
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+
+    let results = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let json = JsonValue::from_str(line).ok()?;
+            let message = json.get::<HashMap<String, JsonValue>>()?;
+            let get_object = |key: &str| message.get(key)?.get::<HashMap<String, JsonValue>>();
+
+            if message.get("reason")?.get::<String>()? != "compiler-artifact"
+                || !*get_object("profile")?.get("test")?.get::<bool>()?
+            {
+                return None;
+            }
+            let name = get_object("target")?.get("name")?.get::<String>()?;
+            let day = Day::from_str(name).ok().filter(|day| days.contains(day))?;
+            let executable = message.get("executable")?.get::<String>()?;
+
+            let passed = Command::new(executable)
+                .arg("--quiet")
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .is_ok_and(|status| status.success());
+            Some((day, passed))
+        })
+        .collect();
+
+    Some(results)
+}
+
+/// Prints each year as a december calendar, starting weeks on monday.
+fn print_calendar(years: &BTreeSet<u16>, statuses: &[DayStatus]) {
+    if years.is_empty() {
+        println!("No solutions yet. Try running \"cargo scaffold <day>\" first.");
+        return;
+    }
+
+    for year in years {
+        println!();
+        println!("{ANSI_BOLD}{year}{ANSI_RESET}");
+        println!("Mo  Tu  We  Th  Fr  Sa  Su");
+
+        let offset = weekday_of_december_first(*year);
+        let mut line = "    ".repeat(offset);
+        for status in statuses.iter().filter(|s| s.day.into_inner().0 == *year) {
+            let (_, day) = status.day.into_inner();
+            line.push_str(&format!("{day:>2}{} ", status.icon()));
+            if (offset + usize::from(day)) % 7 == 0 {
+                println!("{}", line.trim_end());
+                line.clear();
+            }
+        }
+        if !line.is_empty() {
+            println!("{}", line.trim_end());
+        }
+
+        let solved = statuses
+            .iter()
+            .filter(|s| s.day.into_inner().0 == *year)
+            .map(|s| s.verified)
+            .sum::<usize>();
+        // the last day has a single answer.
+        let parts = 2 * usize::from(days_in_event(*year)) - 1;
+        println!("{ANSI_ITALIC}{solved}/{parts} parts verified{ANSI_RESET}");
+    }
+
+    println!();
+    for (icon, name) in LEGEND {
+        println!("{icon} {name}");
+    }
+}
+
+/// The weekday of the 1st of december, counting from monday (0).
+fn weekday_of_december_first(year: u16) -> usize {
+    // Sakamoto's method, which counts from sunday.
+    let y = usize::from(year);
+    let sunday_based = (y + y / 4 - y / 100 + y / 400 + 4 + 1) % 7;
+    (sunday_based + 6) % 7
+}
+
+fn print_json(years: &BTreeSet<u16>, statuses: &[DayStatus]) {
+    let years = years
+        .iter()
+        .map(|year| {
+            let days = statuses
+                .iter()
+                .filter(|s| s.day.into_inner().0 == *year)
+                .map(JsonValue::from)
+                .collect();
+            JsonValue::Object(HashMap::from([
+                ("year".into(), JsonValue::Number(f64::from(*year))),
+                ("days".into(), JsonValue::Array(days)),
+            ]))
+        })
+        .collect();
+
+    let json = JsonValue::Object(HashMap::from([("years".into(), JsonValue::Array(years))]));
+    println!("{}", json.format().unwrap());
+}
+
+impl From<&DayStatus> for JsonValue {
+    fn from(value: &DayStatus) -> Self {
+        let tests = match value.tests {
+            Tests::Passed => JsonValue::String("pass".into()),
+            Tests::Failed => JsonValue::String("fail".into()),
+            Tests::NotRun => JsonValue::Null,
+        };
+
+        JsonValue::Object(HashMap::from([
+            ("day".into(), JsonValue::String(value.day.to_string())),
+            ("scaffolded".into(), JsonValue::Boolean(value.scaffolded)),
+            ("input".into(), JsonValue::Boolean(value.input)),
+            ("examples".into(), JsonValue::Boolean(value.examples)),
+            ("tests".into(), tests),
+            ("verified".into(), JsonValue::Number(value.verified as f64)),
+            ("benchmarked".into(), JsonValue::Boolean(value.benchmarked)),
+        ]))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{answers, weekday_of_december_first};
+    use crate::day;
+
+    #[test]
+    fn counts_answers_of_days() {
+        assert_eq!(answers(day!(2024, 1)), 2);
+        assert_eq!(answers(day!(2024, 24)), 2);
+        assert_eq!(answers(day!(2024, 25)), 1);
+        assert_eq!(answers(day!(2025, 11)), 2);
+        assert_eq!(answers(day!(2025, 12)), 1);
+    }
+
+    #[test]
+    fn finds_weekday_of_december_first() {
+        // 2015-12-01 was a tuesday, 2024-12-01 a sunday.
+        assert_eq!(weekday_of_december_first(2015), 1);
+        assert_eq!(weekday_of_december_first(2020), 1);
+        assert_eq!(weekday_of_december_first(2024), 6);
+    }
+}
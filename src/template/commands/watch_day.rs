@@ -0,0 +1,188 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Output};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::template::config::{config, day_stem};
+use crate::template::examples::{get_metadata_path, try_read_examples};
+use crate::template::input::get_input_path;
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::runner::SUBMITTING;
+use crate::template::{encryption, years, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub fn handle(day: Day, submit_part: Option<u8>) {
+    if !Path::new(&get_path_for_bin(day)).exists() {
+        eprintln!("Solution for {day} does not exist. Try running \"cargo scaffold {day}\" first.");
+        process::exit(1);
+    }
+
+    let mut submit_part = submit_part;
+    let mut last_snapshot = None;
+
+    println!("{ANSI_ITALIC}Watching {day}, press Ctrl+C to stop.{ANSI_RESET}");
+
+    loop {
+        let snapshot = snapshot(&watched_files(day));
+        if last_snapshot.as_ref() != Some(&snapshot) {
+            last_snapshot = Some(snapshot);
+            if run_cycle(day, submit_part) {
+                // only submit once per session, later changes just re-run.
+                submit_part = None;
+            }
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// The solution, the library, the module of its year and every data file of `day`.
+fn watched_files(day: Day) -> Vec<PathBuf> {
+    let mut files = vec![
+        PathBuf::from(get_path_for_bin(day)),
+        PathBuf::from("src/lib.rs"),
+        years::module_path(day.into_inner().0),
+        get_input_path(day, None),
+        encryption::encrypted_path(&get_input_path(day, None)),
+        get_metadata_path(day),
+    ];
+
+    let prefix = day_stem(config().layout, day);
+    if let Ok(entries) = fs::read_dir(config().day_dir("examples", day)) {
+        files.extend(
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.extension().is_some_and(|ext| ext == "txt")
+                        && path
+                            .file_name()
+                            .is_some_and(|name| name.to_string_lossy().starts_with(&prefix))
+                }),
+        );
+    }
+
+    files.sort();
+    files
+}
+
+fn snapshot(files: &[PathBuf]) -> Vec<(PathBuf, Option<SystemTime>)> {
+    files
+        .iter()
+        .map(|path| {
+            let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+            (path.clone(), modified)
+        })
+        .collect()
+}
+
+/// Whether an example of `day` records the expected answer of `part`. Tests of a part without
+/// one pass trivially, e.g. right after scaffolding.
+fn has_expected_answer(day: Day, part: u8) -> bool {
+    try_read_examples(day).is_ok_and(|examples| {
+        examples
+            .iter()
+            .any(|example| example.expected(part).is_some())
+    })
+}
+
+/// Runs the example tests, then the real input. The answer of `submit_part` is submitted if the
+/// tests passed and checked it against an example. Returns whether it was submitted.
+fn run_cycle(day: Day, submit_part: Option<u8>) -> bool {
+    println!();
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+
+    let Some(tests) = run_cargo(&["test", "--quiet", "--bin", &day.to_string()]) else {
+        return false;
+    };
+    let tests_passed = tests.status.success();
+    print_test_summary(&tests, tests_passed);
+
+    if !tests_passed && !compiled(&tests) {
+        return false;
+    }
+
+    let day_str = day.to_string();
+    let mut args = vec!["run", "--quiet", "--release", "--bin", &day_str, "--"];
+    let submit_part = submit_part.filter(|&part| {
+        let checked = has_expected_answer(day, part);
+        if tests_passed && !checked {
+            println!(
+                "{ANSI_ITALIC}Not submitting part {part}, no example records its answer.{ANSI_RESET}"
+            );
+        }
+        tests_passed && checked
+    });
+    let submit_str = submit_part.map(|part| part.to_string());
+    if let Some(submit_str) = &submit_str {
+        args.extend(["--submit", submit_str]);
+    }
+
+    let Some(output) = run_cargo(&args) else {
+        return false;
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for line in stdout.lines() {
+        println!("{line}");
+    }
+    if !output.status.success() {
+        for line in String::from_utf8_lossy(&output.stderr).lines() {
+            eprintln!("{line}");
+        }
+    }
+
+    submit_part.is_some() && output.status.success() && stdout.contains(SUBMITTING)
+}
+
+fn run_cargo(args: &[&str]) -> Option<Output> {
+    match Command::new("cargo").args(args).output() {
+        Ok(output) => Some(output),
+        Err(e) => {
+            eprintln!("Failed to run cargo: {e}");
+            None
+        }
+    }
+}
+
+/// A failed test run either failed to compile or contains failing tests.
+fn compiled(tests: &Output) -> bool {
+    String::from_utf8_lossy(&tests.stdout).contains("test result:")
+}
+
+fn print_test_summary(tests: &Output, passed: bool) {
+    let stdout = String::from_utf8_lossy(&tests.stdout);
+
+    if passed {
+        println!("Tests: ✔");
+        return;
+    }
+
+    if !compiled(tests) {
+        println!("Tests: ✖ (build failed)");
+        for line in String::from_utf8_lossy(&tests.stderr).lines() {
+            eprintln!("{line}");
+        }
+        return;
+    }
+
+    println!("Tests: ✖");
+    // print the panic messages of failed tests, which follow a `---- name stdout ----` header.
+    let mut in_failure = false;
+    for line in stdout.lines() {
+        if let Some(header) = line.strip_prefix("---- ") {
+            in_failure = true;
+            let name = header.split(' ').next().unwrap_or(header);
+            println!("  {name}");
+        } else if line == "failures:" || line == "stack backtrace:" {
+            in_failure = false;
+        } else if in_failure
+            && !line.is_empty()
+            && !line.starts_with("thread '")
+            && !line.starts_with("note:")
+        {
+            println!("    {line}");
+        }
+    }
+}
@@ -0,0 +1,588 @@
+/// Project configuration, read once from `aoc.toml` in the working directory.
+///
+/// Every key is optional and falls back to the layout the template has always used:
+///
+/// ```toml
+/// year = 2024
+/// layout = "flat"
+/// template = "src/template.txt"
+/// templates_dir = "templates"
+/// readme = "README.md"
+/// session_file = "/home/me/.adventofcode.session"
+/// input_key_file = ".input-key"
+/// normalize_inputs = false
+///
+/// [data]
+/// inputs = "data/inputs"
+/// examples = "data/examples"
+/// puzzles = "data/puzzles"
+/// timings = "data/timings.json"
+///
+/// [bench]
+/// target_time_ms = 1000
+/// min_samples = 10
+/// max_samples = 10000
+///
+/// [profiles.alice]
+/// session_file = "/home/me/.alice.session"
+/// ```
+use std::error::Error;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::{fs, io, process};
+
+use toml::{Table, Value};
+
+use crate::template::Day;
+
+static CONFIG_FILE_PATH: &str = "aoc.toml";
+
+/// Paths and settings shared by all commands.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    /// The year of days given without one, e.g. `cargo solve 6`.
+    pub year: Option<u16>,
+    pub layout: Layout,
+    pub inputs_dir: PathBuf,
+    pub examples_dir: PathBuf,
+    pub puzzles_dir: PathBuf,
+    pub timings_file: PathBuf,
+    /// The module template used by `scaffold`.
+    pub template: PathBuf,
+    /// The templates selectable with `scaffold --template <name>`.
+    pub templates_dir: PathBuf,
+    /// The file whose benchmark table is updated by `cargo time --store`.
+    pub readme: PathBuf,
+    /// The session cookie file passed to aoc-cli, aoc-cli's default if unset.
+    pub session_file: Option<PathBuf>,
+    /// The key of the encrypted inputs and puzzles, unless set by `AOC_INPUT_KEY`.
+    pub input_key_file: PathBuf,
+    /// Whether inputs and examples are read with LF line endings and a single trailing newline.
+    pub normalize_inputs: bool,
+    pub bench: BenchConfig,
+    /// Further accounts whose inputs the solutions are checked against, sorted by name.
+    pub profiles: Vec<Profile>,
+}
+
+/// Another account, whose files live in a folder named after it, e.g. `data/inputs/alice`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Profile {
+    pub name: String,
+    /// The session cookie file passed to aoc-cli when downloading for this account. Required,
+    /// so that a profile never downloads the files of the main account.
+    pub session_file: PathBuf,
+}
+
+/// Where the files of a day live.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout {
+    /// `src/bin/2024-05.rs` and `data/inputs/2024-05.txt`.
+    Flat,
+    /// `src/bin/2024/05.rs` and `data/2024/inputs/05.txt`.
+    PerYear,
+}
+
+impl std::str::FromStr for Layout {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "flat" => Ok(Layout::Flat),
+            "per-year" => Ok(Layout::PerYear),
+            _ => Err(invalid("layout")),
+        }
+    }
+}
+
+impl Display for Layout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Layout::Flat => write!(f, "flat"),
+            Layout::PerYear => write!(f, "per-year"),
+        }
+    }
+}
+
+/// How long `cargo time` benches each part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// The approximate total time spent per part.
+    pub target_time: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: None,
+            layout: Layout::Flat,
+            inputs_dir: "data/inputs".into(),
+            examples_dir: "data/examples".into(),
+            puzzles_dir: "data/puzzles".into(),
+            timings_file: "data/timings.json".into(),
+            template: "src/template.txt".into(),
+            templates_dir: "templates".into(),
+            readme: "README.md".into(),
+            session_file: None,
+            input_key_file: ".input-key".into(),
+            normalize_inputs: false,
+            bench: BenchConfig::default(),
+            profiles: vec![],
+        }
+    }
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            target_time: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10000,
+        }
+    }
+}
+
+/// Returns the project configuration. Exits if `aoc.toml` is invalid.
+pub fn config() -> &'static Config {
+    static CONFIG: OnceLock<Config> = OnceLock::new();
+    CONFIG.get_or_init(|| match Config::read_from_file() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Invalid configuration \"{CONFIG_FILE_PATH}\": {e}");
+            process::exit(1);
+        }
+    })
+}
+
+impl Config {
+    /// Reads `aoc.toml`. If not present, returns the defaults.
+    pub fn read_from_file() -> Result<Self, ConfigError> {
+        match fs::read_to_string(CONFIG_FILE_PATH) {
+            Ok(s) => s.parse(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(ConfigError::Io(e)),
+        }
+    }
+
+    /// The directory of a data folder, e.g. `inputs` or `examples`.
+    pub fn data_dir(&self, folder: &str) -> PathBuf {
+        match folder {
+            "inputs" => self.inputs_dir.clone(),
+            "examples" => self.examples_dir.clone(),
+            "puzzles" => self.puzzles_dir.clone(),
+            folder => PathBuf::from("data").join(folder),
+        }
+    }
+
+    /// The directory of the files of `day` in a data folder.
+    pub fn day_dir(&self, folder: &str, day: Day) -> PathBuf {
+        self.layout_day_dir(self.layout, folder, day)
+    }
+
+    /// The path of the file of `day` in a data folder, with a suffix like `.txt` or `-2.txt`.
+    pub fn day_file(&self, folder: &str, day: Day, suffix: &str) -> PathBuf {
+        self.layout_day_file(self.layout, folder, day, suffix)
+    }
+
+    /// Same as [`Config::day_file`] for the files of a profile.
+    pub fn profile_day_file(&self, folder: &str, profile: &str, day: Day, suffix: &str) -> PathBuf {
+        self.day_dir(folder, day)
+            .join(profile)
+            .join(format!("{}{suffix}", day_stem(self.layout, day)))
+    }
+
+    /// Looks up a profile by name.
+    pub fn profile(&self, name: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    /// The path of the solution of `day`.
+    pub fn bin_path(&self, day: Day) -> PathBuf {
+        layout_bin_path(self.layout, day)
+    }
+
+    /// Same as [`Config::day_dir`] in `layout`.
+    pub fn layout_day_dir(&self, layout: Layout, folder: &str, day: Day) -> PathBuf {
+        let dir = self.data_dir(folder);
+        match layout {
+            Layout::Flat => dir,
+            // `data/inputs` becomes `data/2024/inputs`.
+            Layout::PerYear => {
+                let year = day.into_inner().0.to_string();
+                match (dir.parent(), dir.file_name()) {
+                    (Some(parent), Some(name)) => parent.join(year).join(name),
+                    _ => dir.join(year),
+                }
+            }
+        }
+    }
+
+    /// Same as [`Config::day_file`] in `layout`.
+    pub fn layout_day_file(&self, layout: Layout, folder: &str, day: Day, suffix: &str) -> PathBuf {
+        self.layout_day_dir(layout, folder, day)
+            .join(format!("{}{suffix}", day_stem(layout, day)))
+    }
+}
+
+/// The name of the files of `day` in `layout`: `2024-05` or `05`.
+pub fn day_stem(layout: Layout, day: Day) -> String {
+    match layout {
+        Layout::Flat => day.to_string(),
+        Layout::PerYear => format!("{:02}", day.into_inner().1),
+    }
+}
+
+/// The path of the solution of `day` in `layout`.
+pub fn layout_bin_path(layout: Layout, day: Day) -> PathBuf {
+    let (year, day_number) = day.into_inner();
+    match layout {
+        Layout::Flat => Path::new("src/bin").join(format!("{day}.rs")),
+        Layout::PerYear => Path::new("src/bin")
+            .join(year.to_string())
+            .join(format!("{day_number:02}.rs")),
+    }
+}
+
+/// Sets the default year in `aoc.toml`, keeping the rest of the file as is.
+pub fn write_year(year: u16) -> Result<(), ConfigError> {
+    write_key("year", &year.to_string())
+}
+
+/// Sets the layout in `aoc.toml`, keeping the rest of the file as is.
+pub fn write_layout(layout: Layout) -> Result<(), ConfigError> {
+    write_key("layout", &format!("\"{layout}\""))
+}
+
+fn write_key(key: &str, value: &str) -> Result<(), ConfigError> {
+    let contents = match fs::read_to_string(CONFIG_FILE_PATH) {
+        Ok(s) => s,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(ConfigError::Io(e)),
+    };
+    let contents = with_key(&contents, key, value);
+    contents.parse::<Config>()?;
+    fs::write(CONFIG_FILE_PATH, contents).map_err(ConfigError::Io)
+}
+
+/// Replaces the top-level `key` of `contents`, or inserts it at the start.
+fn with_key(contents: &str, key: &str, value: &str) -> String {
+    let mut lines: Vec<String> = contents.lines().map(String::from).collect();
+    let top_level = lines
+        .iter()
+        .position(|line| line.trim_start().starts_with('['))
+        .unwrap_or(lines.len());
+    let is_key = |line: &String| line.split('=').next().is_some_and(|k| k.trim() == key);
+
+    match lines[..top_level].iter().position(is_key) {
+        Some(index) => lines[index] = format!("{key} = {value}"),
+        None => {
+            // append to the top-level keys, in front of the blank lines separating the tables.
+            let end = lines[..top_level]
+                .iter()
+                .rposition(|line| !line.trim().is_empty())
+                .map_or(0, |index| index + 1);
+            lines.insert(end, format!("{key} = {value}"));
+        }
+    }
+
+    lines.join("\n") + "\n"
+}
+
+impl std::str::FromStr for Config {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut table: Table = s.parse().map_err(ConfigError::Toml)?;
+        let mut config = Config::default();
+
+        if let Some(year) = take_integer(&mut table, "year")? {
+            config.year = Some(u16::try_from(year).map_err(|_| invalid("year"))?);
+        }
+        match table.remove("layout") {
+            Some(Value::String(layout)) => config.layout = layout.parse()?,
+            Some(_) => return Err(invalid("layout")),
+            None => {}
+        }
+        take_path(&mut table, "template", &mut config.template)?;
+        take_path(&mut table, "templates_dir", &mut config.templates_dir)?;
+        take_path(&mut table, "readme", &mut config.readme)?;
+        match table.remove("session_file") {
+            Some(Value::String(path)) => config.session_file = Some(path.into()),
+            Some(_) => return Err(invalid("session_file")),
+            None => {}
+        }
+        take_path(&mut table, "input_key_file", &mut config.input_key_file)?;
+        if let Some(normalize) = take_bool(&mut table, "normalize_inputs")? {
+            config.normalize_inputs = normalize;
+        }
+
+        if let Some(mut data) = take_table(&mut table, "data")? {
+            take_path(&mut data, "inputs", &mut config.inputs_dir)?;
+            take_path(&mut data, "examples", &mut config.examples_dir)?;
+            take_path(&mut data, "puzzles", &mut config.puzzles_dir)?;
+            take_path(&mut data, "timings", &mut config.timings_file)?;
+            reject_unknown(&data, "data.")?;
+        }
+
+        if let Some(mut bench) = take_table(&mut table, "bench")? {
+            let bench_config = &mut config.bench;
+            if let Some(ms) = take_integer(&mut bench, "target_time_ms")? {
+                let ms = u64::try_from(ms).map_err(|_| invalid("bench.target_time_ms"))?;
+                bench_config.target_time = Duration::from_millis(ms);
+            }
+            if let Some(n) = take_integer(&mut bench, "min_samples")? {
+                bench_config.min_samples =
+                    u128::try_from(n).map_err(|_| invalid("bench.min_samples"))?;
+            }
+            if let Some(n) = take_integer(&mut bench, "max_samples")? {
+                bench_config.max_samples =
+                    u128::try_from(n).map_err(|_| invalid("bench.max_samples"))?;
+            }
+            reject_unknown(&bench, "bench.")?;
+            if bench_config.min_samples == 0 || bench_config.min_samples > bench_config.max_samples
+            {
+                return Err(invalid("bench.min_samples"));
+            }
+        }
+
+        if let Some(profiles) = take_table(&mut table, "profiles")? {
+            for (name, profile) in profiles {
+                let key = format!("profiles.{name}");
+                // profiles name folders next to the files of the main account.
+                if name.is_empty()
+                    || name == "default"
+                    || !name
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+                {
+                    return Err(invalid(&key));
+                }
+                let Value::Table(mut profile) = profile else {
+                    return Err(invalid(&key));
+                };
+                let session_file = match profile.remove("session_file") {
+                    Some(Value::String(path)) => path.into(),
+                    Some(_) => return Err(invalid(&format!("{key}.session_file"))),
+                    None => return Err(ConfigError::MissingKey(format!("{key}.session_file"))),
+                };
+                reject_unknown(&profile, &format!("{key}."))?;
+                config.profiles.push(Profile { name, session_file });
+            }
+            config.profiles.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+
+        reject_unknown(&table, "")?;
+        Ok(config)
+    }
+}
+
+fn take_integer(table: &mut Table, key: &str) -> Result<Option<i64>, ConfigError> {
+    match table.remove(key) {
+        None => Ok(None),
+        Some(Value::Integer(n)) => Ok(Some(n)),
+        Some(_) => Err(invalid(key)),
+    }
+}
+
+fn take_bool(table: &mut Table, key: &str) -> Result<Option<bool>, ConfigError> {
+    match table.remove(key) {
+        None => Ok(None),
+        Some(Value::Boolean(b)) => Ok(Some(b)),
+        Some(_) => Err(invalid(key)),
+    }
+}
+
+fn take_table(table: &mut Table, key: &str) -> Result<Option<Table>, ConfigError> {
+    match table.remove(key) {
+        None => Ok(None),
+        Some(Value::Table(t)) => Ok(Some(t)),
+        Some(_) => Err(invalid(key)),
+    }
+}
+
+fn take_path(table: &mut Table, key: &str, path: &mut PathBuf) -> Result<(), ConfigError> {
+    match table.remove(key) {
+        None => Ok(()),
+        Some(Value::String(s)) => {
+            *path = s.into();
+            Ok(())
+        }
+        Some(_) => Err(invalid(key)),
+    }
+}
+
+fn reject_unknown(table: &Table, prefix: &str) -> Result<(), ConfigError> {
+    match table.keys().next() {
+        Some(key) => Err(ConfigError::UnknownKey(format!("{prefix}{key}"))),
+        None => Ok(()),
+    }
+}
+
+fn invalid(key: &str) -> ConfigError {
+    ConfigError::InvalidValue(key.to_string())
+}
+
+/// An error which can be returned when reading the configuration.
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+    UnknownKey(String),
+    MissingKey(String),
+    InvalidValue(String),
+}
+
+impl Error for ConfigError {}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Io(e) => write!(f, "{e}"),
+            ConfigError::Toml(e) => write!(f, "{e}"),
+            ConfigError::UnknownKey(key) => write!(f, "unknown key `{key}`."),
+            ConfigError::MissingKey(key) => write!(f, "missing key `{key}`."),
+            ConfigError::InvalidValue(key) => write!(f, "invalid value for `{key}`."),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use super::{with_key, Config, ConfigError, Layout};
+
+    #[test]
+    fn uses_defaults() {
+        let config: Config = "".parse().unwrap();
+        assert_eq!(config, Config::default());
+        assert_eq!(config.data_dir("inputs"), PathBuf::from("data/inputs"));
+        assert_eq!(config.data_dir("other"), PathBuf::from("data/other"));
+    }
+
+    #[test]
+    fn parses_overrides() {
+        let config: Config = r#"
+            year = 2023
+            session_file = ".session"
+            input_key_file = "secrets/key"
+            normalize_inputs = true
+
+            [data]
+            inputs = "inputs"
+            timings = "timings.json"
+
+            [bench]
+            target_time_ms = 250
+            max_samples = 100
+
+            [profiles.bob]
+            session_file = ".bob"
+
+            [profiles.alice]
+            session_file = ".alice"
+        "#
+        .parse()
+        .unwrap();
+        assert_eq!(config.year, Some(2023));
+        assert_eq!(config.session_file, Some(PathBuf::from(".session")));
+        assert_eq!(config.input_key_file, PathBuf::from("secrets/key"));
+        assert!(config.normalize_inputs);
+        assert_eq!(config.inputs_dir, PathBuf::from("inputs"));
+        assert_eq!(config.examples_dir, PathBuf::from("data/examples"));
+        assert_eq!(config.timings_file, PathBuf::from("timings.json"));
+        assert_eq!(config.bench.target_time, Duration::from_millis(250));
+        assert_eq!(config.bench.min_samples, 10);
+        assert_eq!(config.bench.max_samples, 100);
+        let names: Vec<&str> = config.profiles.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["alice", "bob"]);
+        assert_eq!(
+            config.profile("alice").unwrap().session_file,
+            PathBuf::from(".alice")
+        );
+        assert_eq!(
+            config.profile_day_file("inputs", "bob", crate::day!(2024, 5), ".txt"),
+            PathBuf::from("inputs/bob/2024-05.txt")
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        assert!(matches!(
+            "yaer = 2023".parse::<Config>(),
+            Err(ConfigError::UnknownKey(_))
+        ));
+        assert!(matches!(
+            "[data]\ninput = \"x\"".parse::<Config>(),
+            Err(ConfigError::UnknownKey(_))
+        ));
+        assert!(matches!(
+            "year = \"2023\"".parse::<Config>(),
+            Err(ConfigError::InvalidValue(_))
+        ));
+        assert!(matches!(
+            "session_file = 1".parse::<Config>(),
+            Err(ConfigError::InvalidValue(_))
+        ));
+        assert!(matches!(
+            "[bench]\nmin_samples = 0".parse::<Config>(),
+            Err(ConfigError::InvalidValue(_))
+        ));
+        assert!(matches!(
+            "[profiles.\"../bob\"]".parse::<Config>(),
+            Err(ConfigError::InvalidValue(_))
+        ));
+        assert!(matches!(
+            "[profiles.bob]\nsession_file = \"x\"\nsession = \"x\"".parse::<Config>(),
+            Err(ConfigError::UnknownKey(_))
+        ));
+        assert!(matches!(
+            "[profiles.bob]".parse::<Config>(),
+            Err(ConfigError::MissingKey(_))
+        ));
+    }
+
+    #[test]
+    fn replaces_keys() {
+        let config = "# comment\nyear = 2023\n\n[data]\nyear = 1\n";
+        assert_eq!(
+            with_key(config, "year", "2025"),
+            "# comment\nyear = 2025\n\n[data]\nyear = 1\n"
+        );
+        assert_eq!(
+            with_key("[bench]\n", "year", "2025"),
+            "year = 2025\n[bench]\n"
+        );
+        assert_eq!(with_key("", "year", "2025"), "year = 2025\n");
+        assert_eq!(
+            with_key(config, "layout", "\"per-year\""),
+            "# comment\nyear = 2023\nlayout = \"per-year\"\n\n[data]\nyear = 1\n"
+        );
+    }
+
+    #[test]
+    fn resolves_day_paths() {
+        let day = crate::day!(2024, 5);
+        let flat = Config::default();
+        assert_eq!(
+            flat.day_file("inputs", day, ".txt"),
+            PathBuf::from("data/inputs/2024-05.txt")
+        );
+        assert_eq!(flat.bin_path(day), PathBuf::from("src/bin/2024-05.rs"));
+
+        let per_year: Config = "layout = \"per-year\"".parse().unwrap();
+        assert_eq!(per_year.layout, Layout::PerYear);
+        assert_eq!(
+            per_year.day_file("examples", day, "-2.txt"),
+            PathBuf::from("data/2024/examples/05-2.txt")
+        );
+        assert_eq!(per_year.bin_path(day), PathBuf::from("src/bin/2024/05.rs"));
+        assert!("layout = \"nested\"".parse::<Config>().is_err());
+    }
+}
@@ -0,0 +1,564 @@
+/// Checks a solution against a slow reference implementation on random inputs.
+///
+/// A day registers a [`Crosscheck`] with `solution!(2024, 7, crosscheck: crosscheck)`, where
+/// `crosscheck` is a function that returns the input generator and the reference of each part.
+/// `cargo crosscheck <day>` then runs both on random inputs. If they disagree, the input is
+/// shrunk to a minimal one that still makes them disagree, and saved as a new example with the
+/// answers of the reference.
+use std::cell::Cell;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{env, process};
+
+use toml::Value;
+
+use crate::template::answer::Answer;
+use crate::template::config::{config, day_stem};
+use crate::template::examples::get_metadata_path;
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+/// The solution of one part, with its answer normalized.
+pub type Solve = fn(&str) -> Option<Answer>;
+
+type Reference = dyn Fn(&str) -> Option<Answer>;
+
+/// A part with its solution and reference.
+type Check<'a> = (u8, Solve, &'a Reference);
+
+/// Limits how often a failing input is re-checked while shrinking it.
+const MAX_SHRINK_CHECKS: usize = 10_000;
+
+/// A small, seedable random number generator for input generators (SplitMix64).
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`.
+    ///
+    /// # Panics
+    /// Panics if `range` is empty.
+    pub fn range(&mut self, range: Range<u64>) -> u64 {
+        assert!(
+            !range.is_empty(),
+            "cannot pick a number from an empty range"
+        );
+        range.start + self.next_u64() % (range.end - range.start)
+    }
+
+    pub fn bool(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    /// A random element of `items`.
+    ///
+    /// # Panics
+    /// Panics if `items` is empty.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.range(0..items.len() as u64) as usize]
+    }
+}
+
+/// The input generator and reference implementations of a day.
+pub struct Crosscheck {
+    generate: Box<dyn Fn(&mut Rng) -> String>,
+    references: Vec<(u8, Box<Reference>)>,
+}
+
+impl Crosscheck {
+    /// Generates the inputs with `generate`, which should only produce valid puzzle inputs.
+    pub fn new(generate: impl Fn(&mut Rng) -> String + 'static) -> Self {
+        Self {
+            generate: Box::new(generate),
+            references: vec![],
+        }
+    }
+
+    pub fn part_one<T: Into<Answer>>(
+        self,
+        reference: impl Fn(&str) -> Option<T> + 'static,
+    ) -> Self {
+        self.reference(1, reference)
+    }
+
+    pub fn part_two<T: Into<Answer>>(
+        self,
+        reference: impl Fn(&str) -> Option<T> + 'static,
+    ) -> Self {
+        self.reference(2, reference)
+    }
+
+    fn reference<T: Into<Answer>>(
+        mut self,
+        part: u8,
+        reference: impl Fn(&str) -> Option<T> + 'static,
+    ) -> Self {
+        self.references.push((
+            part,
+            Box::new(move |input| reference(input).map(Into::into)),
+        ));
+        self
+    }
+
+    fn reference_of(&self, part: u8) -> Option<&Reference> {
+        self.references
+            .iter()
+            .find(|(p, _)| *p == part)
+            .map(|(_, reference)| reference.as_ref())
+    }
+}
+
+/// The arguments of a crosscheck run, passed by `cargo crosscheck`.
+pub struct Settings {
+    pub iterations: u64,
+    pub seed: u64,
+    pub part: Option<u8>,
+}
+
+impl Settings {
+    /// Parses `--crosscheck <iterations> [--seed <seed>] [--part <part>]`. Returns [`None`] if
+    /// no crosscheck was requested, exits on invalid arguments.
+    pub fn from_args() -> Option<Self> {
+        let args: Vec<String> = env::args().collect();
+        let value = |name: &str| {
+            let index = args.iter().position(|x| x == name)?;
+            match args.get(index + 1).map(|x| x.parse::<u64>()) {
+                Some(Ok(value)) => Some(value),
+                _ => {
+                    eprintln!(
+                        "Unexpected command-line input. Format: cargo crosscheck 7 --iterations 100"
+                    );
+                    process::exit(1);
+                }
+            }
+        };
+
+        let iterations = value("--crosscheck")?;
+        let seed = value("--seed").unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |time| time.as_nanos() as u64)
+        });
+        let part = match value("--part") {
+            None => None,
+            Some(part @ (1 | 2)) => Some(part as u8),
+            Some(part) => {
+                eprintln!("Unexpected part {part}, expecting 1 or 2.");
+                process::exit(1);
+            }
+        };
+
+        Some(Self {
+            iterations,
+            seed,
+            part,
+        })
+    }
+}
+
+/// Runs the crosscheck of `day` if requested by the arguments, and exits afterwards.
+pub fn run_if_requested(
+    day: Day,
+    crosscheck: Option<fn() -> Crosscheck>,
+    solutions: &[(u8, Solve)],
+    validate: Option<fn(&str) -> bool>,
+) {
+    let Some(settings) = Settings::from_args() else {
+        return;
+    };
+    let Some(crosscheck) = crosscheck else {
+        eprintln!("{day} has no crosscheck. Pass `crosscheck: <fn>` to `solution!` to add one.");
+        process::exit(1);
+    };
+
+    let passed = run(day, &crosscheck(), solutions, validate, &settings);
+    process::exit(if passed { 0 } else { 1 });
+}
+
+/// What [`find_mismatch`] found if not every input agreed.
+#[derive(Debug, PartialEq)]
+enum Finding {
+    /// `part` differs from its reference on the input of `seed`, shrunk to `input`.
+    Mismatch { seed: u64, part: u8, input: String },
+    /// The generator or a reference failed, which has been reported already.
+    HarnessError,
+}
+
+/// The outcome of running a solution, with the message of a panic as error.
+type Outcome = Result<Option<Answer>, String>;
+
+fn outcome(solve: &Reference, input: &str) -> Outcome {
+    panic::catch_unwind(AssertUnwindSafe(|| solve(input))).map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(ToString::to_string)
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "panicked".to_string())
+    })
+}
+
+fn describe(outcome: &Outcome) -> String {
+    match outcome {
+        Ok(Some(answer)) => answer.to_string(),
+        Ok(None) => "no answer".to_string(),
+        Err(message) => format!("panicked: {message}"),
+    }
+}
+
+/// Compares the solutions with the references on `settings.iterations` random inputs. Returns
+/// whether they always agreed.
+fn run(
+    day: Day,
+    crosscheck: &Crosscheck,
+    solutions: &[(u8, Solve)],
+    validate: Option<fn(&str) -> bool>,
+    settings: &Settings,
+) -> bool {
+    let checks: Vec<Check> = solutions
+        .iter()
+        .filter(|(part, _)| settings.part.is_none_or(|selected| selected == *part))
+        .filter_map(|&(part, solve)| Some((part, solve, crosscheck.reference_of(part)?)))
+        .collect();
+    if checks.is_empty() {
+        eprintln!("{day} has no reference for the selected parts.");
+        return false;
+    }
+
+    println!(
+        "Checking {} random inputs of {day} (seed {})...",
+        settings.iterations, settings.seed
+    );
+    // the solutions are expected to panic while shrinking, keep the output readable.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let mismatch = find_mismatch(crosscheck, &checks, validate, settings);
+    panic::set_hook(hook);
+
+    let (seed, part, input) = match mismatch {
+        None => {
+            println!(
+                "🎄 The solutions agree with the reference on {} random inputs.",
+                settings.iterations
+            );
+            return true;
+        }
+        Some(Finding::HarnessError) => return false,
+        Some(Finding::Mismatch { seed, part, input }) => (seed, part, input),
+    };
+
+    let (_, solve, reference) = checks.iter().find(|(p, ..)| *p == part).unwrap();
+    println!(
+        "{ANSI_BOLD}Part {part} differs from the reference{ANSI_RESET} on the input of seed {seed}, shrunk to:"
+    );
+    if input.trim_end().is_empty() {
+        println!("(empty input)");
+    } else {
+        println!("{}", input.trim_end());
+    }
+    println!("Expected: {}", describe(&outcome(*reference, &input)));
+    println!("Actual: {}", describe(&outcome(solve, &input)));
+
+    let answers: Vec<(u8, Answer)> = checks
+        .iter()
+        .filter_map(|(part, _, reference)| Some((*part, outcome(*reference, &input).ok()??)))
+        .collect();
+    match save_example(day, &input, seed, &answers) {
+        Ok(path) => println!("Saved the input as a new example \"{}\".", path.display()),
+        Err(e) => eprintln!("Failed to save the input as an example: {e}"),
+    }
+    false
+}
+
+/// Finds the first random input on which a part differs from its reference, and shrinks it.
+/// Failures of the generator or a reference are reported here.
+fn find_mismatch(
+    crosscheck: &Crosscheck,
+    checks: &[Check],
+    validate: Option<fn(&str) -> bool>,
+    settings: &Settings,
+) -> Option<Finding> {
+    for i in 0..settings.iterations {
+        let seed = settings.seed.wrapping_add(i);
+        let input = (crosscheck.generate)(&mut Rng::new(seed));
+        if validate.is_some_and(|validate| !validate(&input)) {
+            eprintln!("The generator produced a malformed input (seed {seed}):\n{input}");
+            return Some(Finding::HarnessError);
+        }
+
+        for &(part, solve, reference) in checks {
+            let expected = match outcome(reference, &input) {
+                Ok(expected) => expected,
+                Err(message) => {
+                    eprintln!(
+                        "The reference of part {part} panicked on the input of seed {seed}: {message}\n{input}"
+                    );
+                    return Some(Finding::HarnessError);
+                }
+            };
+            if outcome(&solve, &input) == Ok(expected) {
+                continue;
+            }
+
+            let input = shrink(&input, |candidate| {
+                validate.is_none_or(|validate| validate(candidate))
+                    && outcome(reference, candidate)
+                        .is_ok_and(|expected| outcome(&solve, candidate) != Ok(expected))
+            });
+            return Some(Finding::Mismatch { seed, part, input });
+        }
+    }
+    None
+}
+
+/// Shrinks `input` while `fails` holds: removes lines, then words and characters of every
+/// line, and lowers numbers, until nothing can be removed anymore.
+fn shrink(input: &str, fails: impl Fn(&str) -> bool) -> String {
+    let checks = Cell::new(0);
+    let fails = |candidate: &str| {
+        checks.set(checks.get() + 1);
+        checks.get() <= MAX_SHRINK_CHECKS && fails(candidate)
+    };
+
+    let newline = if input.ends_with('\n') { "\n" } else { "" };
+    let join = |lines: &[String]| lines.join("\n") + newline;
+    let fails_with = |lines: &[String], i: usize, line: String| {
+        let mut candidate = lines.to_vec();
+        candidate[i] = line;
+        fails(&join(&candidate))
+    };
+
+    let mut lines: Vec<String> = input.lines().map(ToString::to_string).collect();
+    loop {
+        let before = lines.clone();
+        remove_chunks(&mut lines, |lines| fails(&join(lines)));
+
+        for i in 0..lines.len() {
+            let mut words: Vec<&str> = lines[i].split(' ').collect();
+            remove_chunks(&mut words, |words| fails_with(&lines, i, words.join(" ")));
+            let line = words.join(" ");
+
+            let mut chars: Vec<char> = line.chars().collect();
+            let fails_with_chars = |chars: &[char]| fails_with(&lines, i, chars.iter().collect());
+            remove_chunks(&mut chars, fails_with_chars);
+            let mut line: String = chars.into_iter().collect();
+
+            while let Some(lower) = lower_numbers(&line)
+                .into_iter()
+                .find(|lower| fails_with(&lines, i, lower.clone()))
+            {
+                line = lower;
+            }
+            lines[i] = line;
+        }
+
+        if lines == before || checks.get() > MAX_SHRINK_CHECKS {
+            return join(&lines);
+        }
+    }
+}
+
+/// Removes chunks of `items` while `fails` holds, halving the chunk size down to single items.
+fn remove_chunks<T: Clone>(items: &mut Vec<T>, fails: impl Fn(&[T]) -> bool) {
+    let mut size = items.len().div_ceil(2);
+    while size > 0 {
+        let mut start = 0;
+        while start < items.len() {
+            let end = (start + size).min(items.len());
+            let candidate = [&items[..start], &items[end..]].concat();
+            if fails(&candidate) {
+                *items = candidate;
+            } else {
+                start += size;
+            }
+        }
+        size /= 2;
+    }
+}
+
+/// Variants of `line` in which one number is replaced by a lower one.
+fn lower_numbers(line: &str) -> Vec<String> {
+    let mut numbers = vec![];
+    let mut start = None;
+    for (i, c) in line.char_indices().chain([(line.len(), ' ')]) {
+        match (start, c.is_ascii_digit()) {
+            (None, true) => start = Some(i),
+            (Some(s), false) => {
+                numbers.push(s..i);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    numbers
+        .into_iter()
+        .flat_map(|range| {
+            let n: u128 = line[range.clone()].parse().unwrap_or(0);
+            let mut lower = vec![0, n / 2, n.saturating_sub(1)];
+            lower.dedup();
+            lower
+                .into_iter()
+                .filter(move |lower| *lower < n)
+                .map(move |lower| format!("{}{lower}{}", &line[..range.start], &line[range.end..]))
+        })
+        .collect()
+}
+
+/// Saves `input` as an example of `day` that records `answers`. Returns the path of the input.
+fn save_example(
+    day: Day,
+    input: &str,
+    seed: u64,
+    answers: &[(u8, Answer)],
+) -> Result<PathBuf, io::Error> {
+    let dir = config().day_dir("examples", day);
+    fs::create_dir_all(&dir)?;
+    let stem = day_stem(config().layout, day);
+    let (file, path) = (1..)
+        .map(|n| format!("{stem}-crosscheck-{n}.txt"))
+        .map(|file| (file.clone(), dir.join(file)))
+        .find(|(_, path)| !path.exists())
+        .unwrap();
+    fs::write(&path, input)?;
+
+    let metadata_path = get_metadata_path(day);
+    let metadata = fs::read_to_string(&metadata_path).unwrap_or_default();
+    let mut example = String::new();
+    if !metadata.is_empty() {
+        example.push_str(if metadata.ends_with('\n') {
+            "\n"
+        } else {
+            "\n\n"
+        });
+    }
+    example.push_str(&example_metadata(&file, seed, answers));
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(metadata_path)?
+        .write_all(example.as_bytes())?;
+
+    Ok(path)
+}
+
+fn example_metadata(file: &str, seed: u64, answers: &[(u8, Answer)]) -> String {
+    let mut metadata = format!(
+        "[[example]]\nname = \"crosscheck, seed {seed}\"\nfile = {}\n",
+        Value::String(file.to_string())
+    );
+    for (part, answer) in answers {
+        let key = if *part == 1 { "part_one" } else { "part_two" };
+        let value = match answer {
+            Answer::Number(n) => {
+                i64::try_from(*n).map_or(Value::String(n.to_string()), Value::Integer)
+            }
+            Answer::Text(text) => Value::String(text.clone()),
+        };
+        metadata.push_str(&format!("{key} = {value}\n"));
+    }
+    metadata
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        example_metadata, find_mismatch, lower_numbers, shrink, Check, Crosscheck, Finding, Rng,
+        Settings,
+    };
+    use crate::template::answer::Answer;
+
+    #[test]
+    fn generates_reproducible_numbers() {
+        let numbers: Vec<u64> = (0..100).map(|_| Rng::new(7).range(3..9)).collect();
+        assert!(numbers.iter().all(|n| (3..9).contains(n)));
+
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        assert!((0..10).all(|_| a.next_u64() == b.next_u64()));
+        assert_ne!(Rng::new(7).next_u64(), Rng::new(8).next_u64());
+    }
+
+    #[test]
+    fn shrinks_failing_inputs() {
+        // fails whenever a line sums to at least 10.
+        let fails = |input: &str| {
+            input.lines().any(|line| {
+                line.split(' ')
+                    .filter_map(|n| n.parse::<u32>().ok())
+                    .sum::<u32>()
+                    >= 10
+            })
+        };
+        assert_eq!(shrink("1 2\n3 4 8 2\n5\n", fails), "10\n");
+
+        let fails = |input: &str| input.contains('x');
+        assert_eq!(shrink("ab\ncxd", fails), "x");
+    }
+
+    #[test]
+    fn finds_mismatches() {
+        fn count(input: &str) -> Option<Answer> {
+            Some(Answer::from(input.len() + 1))
+        }
+
+        // a disk map without trailing newline, on which the solution is always off by one.
+        let crosscheck = Crosscheck::new(|rng| rng.range(100..1000).to_string())
+            .part_one(|input: &str| Some(input.len()));
+        let checks: Vec<Check> = vec![(1, count, crosscheck.reference_of(1).unwrap())];
+        let settings = Settings {
+            iterations: 10,
+            seed: 3,
+            part: None,
+        };
+        assert_eq!(
+            find_mismatch(&crosscheck, &checks, None, &settings),
+            Some(Finding::Mismatch {
+                seed: 3,
+                part: 1,
+                input: String::new()
+            })
+        );
+
+        let crosscheck = Crosscheck::new(|_| "1".into())
+            .part_one(|_: &str| -> Option<u8> { panic!("unsupported input") });
+        let checks: Vec<Check> = vec![(1, count, crosscheck.reference_of(1).unwrap())];
+        assert_eq!(
+            find_mismatch(&crosscheck, &checks, None, &settings),
+            Some(Finding::HarnessError)
+        );
+    }
+
+    #[test]
+    fn lowers_numbers() {
+        assert_eq!(
+            lower_numbers("a: 10 1"),
+            ["a: 0 1", "a: 5 1", "a: 9 1", "a: 10 0"]
+        );
+    }
+
+    #[test]
+    fn formats_example_metadata() {
+        let answers = [(1, Answer::from(12)), (2, Answer::from("A\"B"))];
+        assert_eq!(
+            example_metadata("2024-07-crosscheck-1.txt", 5, &answers),
+            "[[example]]\nname = \"crosscheck, seed 5\"\nfile = \"2024-07-crosscheck-1.txt\"\n\
+            part_one = 12\npart_two = 'A\"B'\n"
+        );
+    }
+}
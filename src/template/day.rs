@@ -1,13 +1,27 @@
 use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::template::config::config;
 
 #[cfg(feature = "today")]
 use chrono::{Datelike, FixedOffset, Utc};
 
-#[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
 
+/// The year of the first event.
+pub const FIRST_YEAR: u16 = 2015;
+
+/// The number of puzzles of the event of `year`. Since 2025, events last 12 days.
+pub const fn days_in_event(year: u16) -> u8 {
+    if year >= 2025 {
+        12
+    } else {
+        25
+    }
+}
+
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
 ///
 /// # Display
@@ -25,10 +39,10 @@ impl Day {
     /// Creates a [`Day`] from the provided value if it's in the valid range,
     /// returns [`None`] otherwise.
     pub fn new(year: u16, day: u8) -> Option<Self> {
-        if year < 2015 || year > 2024 {
+        if year < FIRST_YEAR {
             return None;
         }
-        if day == 0 || day > 25 {
+        if day == 0 || day > days_in_event(year) {
             return None;
         }
         Some(Self(year, day))
@@ -48,11 +62,11 @@ impl Day {
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if it's a day of this year's event, `None` otherwise.
     pub fn today() -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
         let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
+        if today.month() == 12 {
             Self::new(
                 u16::try_from(today.year()).ok()?,
                 u8::try_from(today.day()).ok()?,
@@ -96,9 +110,12 @@ impl FromStr for Day {
     type Err = DayFromStrError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts = s.split('-').collect::<Vec<_>>();
-        let year = parts[0].parse().map_err(|_| DayFromStrError)?;
-        let day = parts[1].parse().map_err(|_| DayFromStrError)?;
+        let (year, day) = match s.split_once('-') {
+            Some((year, day)) => (year.parse().map_err(|_| DayFromStrError)?, day),
+            // a bare day number belongs to the configured default year.
+            None => (config().year.ok_or(DayFromStrError)?, s),
+        };
+        let day = day.parse().map_err(|_| DayFromStrError)?;
         Self::new(year, day).ok_or(DayFromStrError)
     }
 }
@@ -112,31 +129,66 @@ impl Error for DayFromStrError {}
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(
-            "expecting a year number from 2015 to 2024 and \
-            a day number between 1 and 25",
+            "expecting a year number from 2015 and a day number of \
+            its event (1 to 25, or 1 to 12 since 2025), e.g. \"2024-05\", \
+            or only a day number if `year` is set in aoc.toml",
         )
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// Today's date as (year, month, day) in UTC-5, the timezone of the puzzle releases.
+pub fn server_date() -> (i64, u32, u32) {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+        .saturating_add_signed(i64::from(SERVER_UTC_OFFSET) * 3600);
+    civil_from_days((secs / 86400) as i64)
+}
+
+/// Converts days since the unix epoch to a (year, month, day) date.
+/// See <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// The year of the latest event that has started.
+pub fn latest_event_year() -> u16 {
+    let (year, month, _) = server_date();
+    let year = if month == 12 { year } else { year - 1 };
+    u16::try_from(year).unwrap_or(FIRST_YEAR)
+}
+
+/// An iterator that yields every day of every event, up to the latest event
+/// or the configured year, whichever is later.
 pub fn all_days() -> AllDays {
-    AllDays::new()
+    AllDays::until(latest_event_year().max(config().year.unwrap_or(FIRST_YEAR)))
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of every event from 2015 to a last year.
 pub struct AllDays {
     current_year: u16,
     current_day: u8,
+    last_year: u16,
 }
 
 impl AllDays {
-    #[allow(clippy::new_without_default)]
-    pub fn new() -> Self {
+    /// Yields the days of the events from 2015 to `last_year`.
+    pub fn until(last_year: u16) -> Self {
         Self {
-            current_year: 2015,
+            current_year: FIRST_YEAR,
             current_day: 1,
+            last_year,
         }
     }
 }
@@ -145,13 +197,13 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current_year > 2024 {
+        if self.current_year > self.last_year {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is within the event.
         let day = Day(self.current_year, self.current_day);
         self.current_day += 1;
-        if self.current_day > 25 && self.current_year <= 2024 {
+        if self.current_day > days_in_event(self.current_year) {
             self.current_year += 1;
             self.current_day = 1;
         }
@@ -167,11 +219,15 @@ impl Iterator for AllDays {
 macro_rules! day {
     ($year:literal, $day:literal) => {{
         const _ASSERT: () = assert!(
-            $day != 0 && $day <= 25 && $year >= 2015 && $year <= 2024,
+            $day != 0
+                && $year >= $crate::template::FIRST_YEAR
+                && $day <= $crate::template::days_in_event($year),
             concat!(
-                "invalid day number `",
+                "invalid day `",
+                $year,
+                "-",
                 $day,
-                "`, expecting a value between 1 and 25"
+                "`, expecting a year from 2015 and a day of its event"
             ),
         );
         $crate::template::Day::__new_unchecked($year, $day)
@@ -182,11 +238,27 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{civil_from_days, days_in_event, AllDays, Day};
+
+    #[test]
+    fn converts_days_to_dates() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+        assert_eq!(civil_from_days(20_062), (2024, 12, 5));
+    }
+
+    #[test]
+    fn validates_days_of_event() {
+        assert_eq!(days_in_event(2024), 25);
+        assert_eq!(days_in_event(2025), 12);
+        assert!(Day::new(2014, 1).is_none());
+        assert!(Day::new(2024, 25).is_some());
+        assert!(Day::new(2025, 13).is_none());
+    }
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
+        let mut iter = AllDays::until(2024);
 
         assert_eq!(iter.next(), Some(Day(2015, 1)));
         assert_eq!(iter.next(), Some(Day(2015, 2)));
@@ -213,7 +285,7 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(2015, 23)));
         assert_eq!(iter.next(), Some(Day(2015, 24)));
         assert_eq!(iter.next(), Some(Day(2015, 25)));
-        assert_eq!(iter.next(), Some(Day(2015, 1)));
+        assert_eq!(iter.next(), Some(Day(2016, 1)));
         assert_eq!(iter.next(), Some(Day(2016, 2)));
         assert_eq!(iter.next(), Some(Day(2016, 3)));
         assert_eq!(iter.next(), Some(Day(2016, 4)));
@@ -0,0 +1,203 @@
+/// Encrypts puzzle inputs and puzzles, so that they can be committed next to the solutions.
+///
+/// An encrypted file lives next to its plaintext with an additional `.enc` extension, e.g.
+/// `data/inputs/2024-05.txt.enc`. Files are encrypted with ChaCha20-Poly1305 using the SHA-256
+/// hash of the key from `AOC_INPUT_KEY` or the configured key file. The nonce is derived from
+/// the key and the contents, so re-encrypting an unchanged file gives the same bytes and does
+/// not show up in diffs.
+use std::error::Error;
+use std::ffi::OsString;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
+
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use sha2::{Digest, Sha256};
+
+use crate::template::config::config;
+
+/// The environment variable that takes precedence over the key file, e.g. in CI.
+pub const KEY_VAR: &str = "AOC_INPUT_KEY";
+
+/// Identifies the format of encrypted files.
+const MAGIC: &[u8] = b"AOCENC1\n";
+const NONCE_LEN: usize = 12;
+
+/// The key that encrypts and decrypts data files.
+#[derive(Clone)]
+pub struct Key([u8; 32]);
+
+impl Key {
+    pub fn new(secret: &str) -> Self {
+        Self(Sha256::digest(secret.trim().as_bytes()).into())
+    }
+
+    /// Reads the key from `AOC_INPUT_KEY` or the configured key file. Returns [`None`] if neither
+    /// is set.
+    pub fn read() -> Result<Option<Self>, EncryptionError> {
+        if let Ok(secret) = env::var(KEY_VAR)
+            && !secret.trim().is_empty()
+        {
+            return Ok(Some(Self::new(&secret)));
+        }
+
+        let path = &config().input_key_file;
+        match fs::read_to_string(path) {
+            Ok(secret) if secret.trim().is_empty() => Err(EncryptionError::EmptyKey(path.clone())),
+            Ok(secret) => Ok(Some(Self::new(&secret))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(EncryptionError::Io(e)),
+        }
+    }
+
+    /// Reads the key, failing if none is set.
+    pub fn require() -> Result<Self, EncryptionError> {
+        Self::read()?.ok_or(EncryptionError::MissingKey)
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        hasher.update(self.0);
+        hasher.update(plaintext);
+        let nonce = hasher.finalize();
+        let nonce = Nonce::from_slice(&nonce[..NONCE_LEN]);
+
+        let ciphertext = ChaCha20Poly1305::new((&self.0).into())
+            .encrypt(nonce, plaintext)
+            .expect("plaintext should not exceed the maximum length");
+
+        [MAGIC, nonce.as_slice(), &ciphertext].concat()
+    }
+
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let data = data
+            .strip_prefix(MAGIC)
+            .ok_or(EncryptionError::InvalidFile)?;
+        if data.len() < NONCE_LEN {
+            return Err(EncryptionError::InvalidFile);
+        }
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+
+        ChaCha20Poly1305::new((&self.0).into())
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| EncryptionError::WrongKey)
+    }
+}
+
+/// The path of the encrypted counterpart of `path`.
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(".enc");
+    PathBuf::from(name)
+}
+
+/// Whether `path` or its encrypted counterpart exists.
+pub fn exists(path: &Path) -> bool {
+    path.exists() || encrypted_path(path).exists()
+}
+
+/// Reads `path`, or decrypts its encrypted counterpart if only that exists. Fails with
+/// [`io::ErrorKind::NotFound`] if neither exists.
+pub fn read_to_string(path: &Path) -> Result<String, io::Error> {
+    match fs::read_to_string(path) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound && encrypted_path(path).exists() => {
+            let data = fs::read(encrypted_path(path))?;
+            let plaintext = Key::require()
+                .and_then(|key| key.decrypt(&data))
+                .map_err(io::Error::other)?;
+            String::from_utf8(plaintext).map_err(io::Error::other)
+        }
+        result => result,
+    }
+}
+
+/// Replaces the plaintext file at `path` by its encrypted counterpart. Returns `false` if there
+/// is no plaintext file.
+pub fn lock(key: &Key, path: &Path) -> Result<bool, io::Error> {
+    let plaintext = match fs::read(path) {
+        Ok(plaintext) => plaintext,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e),
+    };
+    fs::write(encrypted_path(path), key.encrypt(&plaintext))?;
+    fs::remove_file(path)?;
+    Ok(true)
+}
+
+/// Decrypts the encrypted counterpart of `path` to `path`, keeping the encrypted file. Returns
+/// `false` if there is no encrypted file.
+pub fn unlock(key: &Key, path: &Path) -> Result<bool, io::Error> {
+    let data = match fs::read(encrypted_path(path)) {
+        Ok(data) => data,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e),
+    };
+    let plaintext = key.decrypt(&data).map_err(io::Error::other)?;
+    fs::write(path, plaintext)?;
+    Ok(true)
+}
+
+#[derive(Debug)]
+pub enum EncryptionError {
+    MissingKey,
+    EmptyKey(PathBuf),
+    InvalidFile,
+    WrongKey,
+    Io(io::Error),
+}
+
+impl Error for EncryptionError {}
+
+impl Display for EncryptionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EncryptionError::MissingKey => write!(
+                f,
+                "No key to decrypt inputs. Set {KEY_VAR} or write the key to \"{}\".",
+                config().input_key_file.display()
+            ),
+            EncryptionError::EmptyKey(path) => {
+                write!(f, "The key file \"{}\" is empty.", path.display())
+            }
+            EncryptionError::InvalidFile => write!(f, "The file is not an encrypted input."),
+            EncryptionError::WrongKey => write!(f, "The key does not match the encrypted file."),
+            EncryptionError::Io(e) => write!(f, "Could not read the key: {e}"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+
+    use super::{encrypted_path, EncryptionError, Key};
+
+    #[test]
+    fn encrypts_and_decrypts() {
+        let key = Key::new("secret\n");
+        let encrypted = key.encrypt(b"3   4\n4   3\n");
+        assert!(!encrypted.windows(5).any(|w| w == b"3   4"));
+        assert_eq!(key.encrypt(b"3   4\n4   3\n"), encrypted);
+        assert_eq!(key.decrypt(&encrypted).unwrap(), b"3   4\n4   3\n");
+
+        assert!(matches!(
+            Key::new("other").decrypt(&encrypted),
+            Err(EncryptionError::WrongKey)
+        ));
+        assert!(matches!(
+            key.decrypt(b"3   4"),
+            Err(EncryptionError::InvalidFile)
+        ));
+    }
+
+    #[test]
+    fn appends_extension() {
+        assert_eq!(
+            encrypted_path(Path::new("data/inputs/2024-05.txt")),
+            Path::new("data/inputs/2024-05.txt.enc")
+        );
+    }
+}
@@ -0,0 +1,299 @@
+/// Reads example metadata (`data/examples/{day}.toml`) and checks solutions against it.
+///
+/// A metadata file lists any number of examples with their expected answers:
+///
+/// ```toml
+/// [[example]]
+/// part_one = 143
+/// part_two = 123
+///
+/// [[example]]
+/// name = "larger"
+/// file = "2024-05-2.txt"
+/// part_two = "ABC"
+///
+/// [[example]]
+/// input = """
+/// 1 2
+/// 3 4
+/// """
+/// params = { steps = 6 }
+/// part_one = 10
+/// ```
+///
+/// The input is either inline text (`input`), a file in `data/examples` (`file`) or, if neither
+/// is given, `data/examples/{day}.txt`. Parts without an expected answer are not checked.
+/// `params` overrides the day's [`Params`] for this example.
+use std::error::Error;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use toml::{Table, Value};
+
+use crate::template::answer::Answer;
+use crate::template::config::{config, day_stem};
+use crate::template::input::normalize_if_configured;
+use crate::template::params::{Params, ParamsError};
+use crate::template::Day;
+
+/// One example input together with its expected answers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    pub part_one: Option<Answer>,
+    pub part_two: Option<Answer>,
+    /// Parameters that differ from the real input.
+    pub params: Table,
+}
+
+impl Example {
+    /// The expected answer for `part`, if recorded.
+    pub fn expected(&self, part: u8) -> Option<&Answer> {
+        match part {
+            1 => self.part_one.as_ref(),
+            2 => self.part_two.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// The day's parameters with the overrides of this example applied.
+    pub fn params<P: Params>(&self) -> Result<P, ParamsError> {
+        P::with_overrides(self.params.iter().map(|(key, value)| match value {
+            Value::String(s) => (key, s.clone()),
+            value => (key, value.to_string()),
+        }))
+    }
+}
+
+/// Returns the path of the metadata file of `day`.
+pub fn get_metadata_path(day: Day) -> PathBuf {
+    config().day_file("examples", day, ".toml")
+}
+
+/// Reads the examples of `day`. Days without a metadata file have no examples.
+///
+/// # Panics
+/// Panics if the metadata file or a referenced example file is invalid.
+#[must_use]
+pub fn read_examples(day: Day) -> Vec<Example> {
+    try_read_examples(day).unwrap_or_else(|e| {
+        panic!(
+            "invalid example metadata \"{}\": {e}",
+            get_metadata_path(day).display()
+        )
+    })
+}
+
+/// Same as [`read_examples`], returning an error for invalid metadata.
+pub fn try_read_examples(day: Day) -> Result<Vec<Example>, ExamplesError> {
+    let path = get_metadata_path(day);
+    let metadata = match fs::read_to_string(&path) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(source) => return Err(ExamplesError::Io { path, source }),
+    };
+    parse_examples(day, &metadata, &config().day_dir("examples", day))
+}
+
+/// Parses example metadata of `day`, resolving example files relative to `dir`.
+pub fn parse_examples(day: Day, metadata: &str, dir: &Path) -> Result<Vec<Example>, ExamplesError> {
+    let mut table: Table = metadata.parse().map_err(ExamplesError::Toml)?;
+    let examples = match table.remove("example") {
+        Some(Value::Array(examples)) => examples,
+        Some(_) => {
+            return Err(ExamplesError::Invalid(
+                "`example` should be an array of tables".into(),
+            ))
+        }
+        None => vec![],
+    };
+
+    examples
+        .into_iter()
+        .enumerate()
+        .map(|(index, example)| match example {
+            Value::Table(table) => parse_example(day, index, table, dir),
+            _ => Err(ExamplesError::Invalid(format!(
+                "example {} should be a table",
+                index + 1
+            ))),
+        })
+        .collect()
+}
+
+fn parse_example(
+    day: Day,
+    index: usize,
+    mut table: Table,
+    dir: &Path,
+) -> Result<Example, ExamplesError> {
+    let name = match table.remove("name") {
+        Some(Value::String(name)) => name,
+        Some(_) => return Err(invalid_key(index, "name")),
+        None => format!("example {}", index + 1),
+    };
+
+    let input = match (table.remove("input"), table.remove("file")) {
+        (Some(Value::String(input)), None) => input,
+        (None, Some(Value::String(file))) => read_example_file(&dir.join(file))?,
+        (None, None) => {
+            let stem = day_stem(config().layout, day);
+            read_example_file(&dir.join(format!("{stem}.txt")))?
+        }
+        (Some(_), Some(_)) => {
+            return Err(ExamplesError::Invalid(format!(
+                "{name} should set either `input` or `file`, not both"
+            )))
+        }
+        (Some(_), None) => return Err(invalid_key(index, "input")),
+        (None, Some(_)) => return Err(invalid_key(index, "file")),
+    };
+
+    let mut answer = |key: &str| match table.remove(key) {
+        None => Ok(None),
+        Some(Value::Integer(n)) => Ok(Some(Answer::from(n))),
+        Some(Value::String(s)) => Ok(Some(s.parse::<Answer>().unwrap())),
+        Some(_) => Err(invalid_key(index, key)),
+    };
+
+    let part_one = answer("part_one")?;
+    let part_two = answer("part_two")?;
+
+    let params = match table.remove("params") {
+        Some(Value::Table(params)) => params,
+        Some(_) => return Err(invalid_key(index, "params")),
+        None => Table::new(),
+    };
+
+    if let Some(key) = table.keys().next() {
+        return Err(ExamplesError::Invalid(format!(
+            "unknown key `{key}` in example {}",
+            index + 1
+        )));
+    }
+
+    Ok(Example {
+        name,
+        input: normalize_if_configured(input),
+        part_one,
+        part_two,
+        params,
+    })
+}
+
+fn read_example_file(path: &Path) -> Result<String, ExamplesError> {
+    fs::read_to_string(path).map_err(|source| ExamplesError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn invalid_key(index: usize, key: &str) -> ExamplesError {
+    ExamplesError::Invalid(format!(
+        "`{key}` of example {} has an invalid type",
+        index + 1
+    ))
+}
+
+/// Runs `func` on the example of `day` at `index` and compares its answer to `part` with the
+/// recorded one. Solutions without parameters take `()`. `solution!` generates a test calling
+/// this for every example and part that records an answer.
+///
+/// # Panics
+/// Panics if the answer differs from the recorded one, or if the example is missing or invalid.
+pub fn check_example<P: Params, T: Into<Answer>>(
+    day: Day,
+    part: u8,
+    index: usize,
+    func: impl Fn(&str, &P) -> Option<T>,
+) {
+    let examples = read_examples(day);
+    let Some(example) = examples.get(index) else {
+        panic!("example {} of {day} does not exist", index + 1);
+    };
+    let Some(expected) = example.expected(part) else {
+        panic!("{} records no answer to part {part}", example.name);
+    };
+    let params = example
+        .params::<P>()
+        .unwrap_or_else(|e| panic!("{}: {e}", example.name));
+
+    match func(&example.input, &params).map(Into::into) {
+        Some(actual) => assert!(
+            actual.matches(&expected.to_string()),
+            "{}: expected {expected}, got {actual}",
+            example.name
+        ),
+        None => panic!("{}: expected {expected}, got no answer", example.name),
+    }
+}
+
+/// An error which can be returned when reading example metadata.
+#[derive(Debug)]
+pub enum ExamplesError {
+    Toml(toml::de::Error),
+    Io { path: PathBuf, source: io::Error },
+    Invalid(String),
+}
+
+impl Error for ExamplesError {}
+
+impl Display for ExamplesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExamplesError::Toml(e) => write!(f, "{e}"),
+            ExamplesError::Io { path, source } => {
+                write!(f, "could not read \"{}\": {source}", path.display())
+            }
+            ExamplesError::Invalid(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+
+    use super::parse_examples;
+    use crate::day;
+    use crate::template::answer::Answer;
+
+    #[test]
+    fn parses_inline_examples() {
+        let metadata = r#"
+            [[example]]
+            input = "1 2"
+            part_one = 3
+
+            [[example]]
+            name = "letters"
+            input = "abc"
+            part_two = "ABC"
+            params = { size = 7 }
+        "#;
+        let examples = parse_examples(day!(2024, 1), metadata, Path::new(".")).unwrap();
+        assert_eq!(examples.len(), 2);
+        assert_eq!(examples[0].name, "example 1");
+        assert_eq!(examples[0].expected(1), Some(&Answer::from(3)));
+        assert_eq!(examples[0].expected(2), None);
+        assert_eq!(examples[1].name, "letters");
+        assert_eq!(examples[1].expected(2), Some(&Answer::from("ABC")));
+        assert_eq!(examples[1].params["size"].as_integer(), Some(7));
+    }
+
+    #[test]
+    fn rejects_invalid_metadata() {
+        let dir = Path::new(".");
+        assert!(parse_examples(day!(2024, 1), "example = 1", dir).is_err());
+        assert!(parse_examples(day!(2024, 1), "[[example]]\ninput = 1", dir).is_err());
+        assert!(
+            parse_examples(day!(2024, 1), "[[example]]\ninput = \"\"\npart_1 = 1", dir).is_err()
+        );
+        assert!(parse_examples(day!(2024, 1), "[[example]]\nfile = \"missing.txt\"", dir).is_err());
+        assert!(parse_examples(day!(2024, 1), "", dir).unwrap().is_empty());
+    }
+}
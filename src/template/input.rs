@@ -0,0 +1,339 @@
+/// Selects and reads the input a solution runs against.
+use std::error::Error;
+use std::fmt::Display;
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::{env, fs, process};
+
+use toml::Table;
+
+use crate::template::config::config;
+use crate::template::examples::{get_metadata_path, try_read_examples, Example, ExamplesError};
+use crate::template::{encryption, Day};
+
+/// Where the input of a solution comes from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input in the inputs directory, `data/inputs` by default.
+    Puzzle,
+    /// An arbitrary file.
+    Path(PathBuf),
+    /// Everything read from stdin.
+    Stdin,
+    /// The n-th example (counting from 1) of the example metadata.
+    Example(usize),
+}
+
+impl InputSource {
+    /// Reads the source from the arguments passed to the solution:
+    /// `--input <path>`, `--stdin` or `--example <n>`.
+    pub fn from_args(args: &[String]) -> Result<Self, InputError> {
+        let value_of = |flag: &str| {
+            args.iter()
+                .position(|arg| arg == flag)
+                .map(|i| args.get(i + 1).cloned().unwrap_or_default())
+        };
+
+        let mut sources = vec![];
+        if let Some(path) = value_of("--input") {
+            sources.push(InputSource::Path(path.into()));
+        }
+        if args.iter().any(|arg| arg == "--stdin") {
+            sources.push(InputSource::Stdin);
+        }
+        if let Some(n) = value_of("--example") {
+            match n.parse() {
+                Ok(n) if n > 0 => sources.push(InputSource::Example(n)),
+                _ => return Err(InputError::InvalidExample(n)),
+            }
+        }
+
+        match sources.len() {
+            0 => Ok(InputSource::Puzzle),
+            1 => Ok(sources.remove(0)),
+            _ => Err(InputError::Conflicting),
+        }
+    }
+
+    /// The arguments that select this source when passed to a solution.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            InputSource::Puzzle => vec![],
+            InputSource::Path(path) => vec!["--input".into(), path.display().to_string()],
+            InputSource::Stdin => vec!["--stdin".into()],
+            InputSource::Example(n) => vec!["--example".into(), n.to_string()],
+        }
+    }
+}
+
+/// An input, together with the example it belongs to, if any.
+#[derive(Clone, Debug)]
+pub struct Input {
+    pub text: String,
+    pub example: Option<Example>,
+}
+
+/// Returns the path of the puzzle input of `day`, or of the input of another account's `profile`.
+pub fn get_input_path(day: Day, profile: Option<&str>) -> PathBuf {
+    match profile {
+        Some(profile) => config().profile_day_file("inputs", profile, day, ".txt"),
+        None => config().day_file("inputs", day, ".txt"),
+    }
+}
+
+/// Reads the input of `day` from `source`.
+pub fn read_input(day: Day, source: &InputSource) -> Result<Input, InputError> {
+    let read_path = |path: PathBuf, suggest_download: bool| match encryption::read_to_string(&path)
+    {
+        Ok(text) => Ok(text),
+        Err(e) if e.kind() == io::ErrorKind::NotFound && suggest_download => {
+            Err(InputError::MissingPuzzleInput { day, path })
+        }
+        Err(source) => Err(InputError::Io { path, source }),
+    };
+
+    let text = match source {
+        InputSource::Puzzle => read_path(get_input_path(day, None), true)?,
+        InputSource::Path(path) => read_path(path.clone(), false)?,
+        InputSource::Stdin => {
+            let mut text = String::new();
+            io::stdin()
+                .read_to_string(&mut text)
+                .map_err(InputError::Stdin)?;
+            text
+        }
+        // examples are normalized when they are read.
+        InputSource::Example(n) => {
+            let example = read_example(day, *n)?;
+            return Ok(Input {
+                text: example.input.clone(),
+                example: Some(example),
+            });
+        }
+    };
+
+    Ok(Input {
+        text: normalize_if_configured(text),
+        example: None,
+    })
+}
+
+/// Reads the n-th example of the metadata of `day`. Days without a metadata file fall back to
+/// their example files, see [`example_file`].
+fn read_example(day: Day, n: usize) -> Result<Example, InputError> {
+    if !get_metadata_path(day).exists() {
+        let path = example_file(day, n);
+        let input = fs::read_to_string(&path).map_err(|source| InputError::Io {
+            path: path.clone(),
+            source,
+        })?;
+        return Ok(Example {
+            name: path.file_name().unwrap().to_string_lossy().into_owned(),
+            input: normalize_if_configured(input),
+            part_one: None,
+            part_two: None,
+            params: Table::new(),
+        });
+    }
+
+    let mut examples = try_read_examples(day).map_err(|source| InputError::InvalidMetadata {
+        path: get_metadata_path(day),
+        source,
+    })?;
+    if n > examples.len() {
+        return Err(InputError::MissingExample {
+            n,
+            count: examples.len(),
+        });
+    }
+    Ok(examples.swap_remove(n - 1))
+}
+
+/// The n-th example file of `day`: `data/examples/{day}.txt` for the first one, then
+/// `data/examples/{day}-{n}.txt`.
+fn example_file(day: Day, n: usize) -> PathBuf {
+    match n {
+        1 => config().day_file("examples", day, ".txt"),
+        n => config().day_file("examples", day, &format!("-{n}.txt")),
+    }
+}
+
+/// Converts CRLF line endings to LF and ends non-empty text with exactly one newline.
+pub fn normalize(text: &str) -> String {
+    let text = text.replace("\r\n", "\n");
+    let text = text.trim_end_matches('\n');
+    if text.is_empty() {
+        String::new()
+    } else {
+        format!("{text}\n")
+    }
+}
+
+/// Normalizes `text` if `normalize_inputs` is set in `aoc.toml`, see [`normalize`].
+pub fn normalize_if_configured(text: String) -> String {
+    if config().normalize_inputs {
+        normalize(&text)
+    } else {
+        text
+    }
+}
+
+/// Checks the input with the validator passed to `solution!`, exits with its message if the
+/// input is malformed.
+pub fn validate_or_exit<E: Display>(
+    day: Day,
+    input: &Input,
+    validate: impl Fn(&str) -> Result<(), E>,
+) {
+    if let Err(e) = validate(&input.text) {
+        match &input.example {
+            Some(example) => eprintln!("Malformed input of {day}, {}: {e}", example.name),
+            None => eprintln!("Malformed input of {day}: {e}"),
+        }
+        process::exit(1);
+    }
+}
+
+/// Checks an example with the validator passed to `solution!`.
+///
+/// # Panics
+/// Panics with the message of the validator if the example is malformed.
+pub fn validate_example<E: Display>(input: &str, validate: impl Fn(&str) -> Result<(), E>) {
+    if let Err(e) = validate(input) {
+        panic!("malformed example input: {e}");
+    }
+}
+
+/// Reads the input selected by the arguments passed to the solution, exits on failure.
+pub fn from_args(day: Day) -> Input {
+    let args: Vec<String> = env::args().collect();
+    match InputSource::from_args(&args).and_then(|source| read_input(day, &source)) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// An error which can be returned when reading an input.
+#[derive(Debug)]
+pub enum InputError {
+    MissingPuzzleInput {
+        day: Day,
+        path: PathBuf,
+    },
+    MissingExample {
+        n: usize,
+        count: usize,
+    },
+    InvalidMetadata {
+        path: PathBuf,
+        source: ExamplesError,
+    },
+    InvalidExample(String),
+    Conflicting,
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Stdin(io::Error),
+}
+
+impl Error for InputError {}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::MissingPuzzleInput { day, path } => write!(
+                f,
+                "Could not find the input file \"{}\". Try running \"cargo download {day}\" to download it.",
+                path.display()
+            ),
+            InputError::MissingExample { n, count } => write!(
+                f,
+                "Could not find example {n}, the example metadata lists {count} example(s)."
+            ),
+            InputError::InvalidMetadata { path, source } => {
+                write!(f, "Invalid example metadata \"{}\": {source}", path.display())
+            }
+            InputError::InvalidExample(n) => {
+                write!(f, "Expecting an example number starting at 1, found \"{n}\".")
+            }
+            InputError::Conflicting => {
+                write!(f, "Only one of --input, --stdin and --example can be used at a time.")
+            }
+            InputError::Io { path, source } => {
+                write!(f, "Could not read \"{}\": {source}", path.display())
+            }
+            InputError::Stdin(source) => write!(f, "Could not read stdin: {source}"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{example_file, normalize, InputError, InputSource};
+
+    fn parse(args: &[&str]) -> Result<InputSource, InputError> {
+        let args: Vec<String> = args.iter().map(ToString::to_string).collect();
+        InputSource::from_args(&args)
+    }
+
+    #[test]
+    fn normalizes_text() {
+        assert_eq!(normalize("1 2\r\n3 4\r\n"), "1 2\n3 4\n");
+        assert_eq!(normalize("1 2\n3 4"), "1 2\n3 4\n");
+        assert_eq!(normalize("1 2\n\n\n"), "1 2\n");
+        assert_eq!(normalize("  1\n\n2  \n"), "  1\n\n2  \n");
+        assert_eq!(normalize("\r\n"), "");
+    }
+
+    #[test]
+    fn parses_sources() {
+        assert_eq!(parse(&["bin"]).unwrap(), InputSource::Puzzle);
+        assert_eq!(
+            parse(&["bin", "--input", "in.txt"]).unwrap(),
+            InputSource::Path("in.txt".into())
+        );
+        assert_eq!(parse(&["bin", "--stdin"]).unwrap(), InputSource::Stdin);
+        assert_eq!(
+            parse(&["bin", "--example", "2"]).unwrap(),
+            InputSource::Example(2)
+        );
+
+        let source = InputSource::Path("in.txt".into());
+        let mut args = vec!["bin".to_string()];
+        args.extend(source.to_args());
+        assert_eq!(InputSource::from_args(&args).unwrap(), source);
+    }
+
+    #[test]
+    fn names_example_files() {
+        let day = crate::day!(2024, 5);
+        assert_eq!(
+            example_file(day, 1),
+            PathBuf::from("data/examples/2024-05.txt")
+        );
+        assert_eq!(
+            example_file(day, 2),
+            PathBuf::from("data/examples/2024-05-2.txt")
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_sources() {
+        assert!(matches!(
+            parse(&["bin", "--example", "0"]),
+            Err(InputError::InvalidExample(_))
+        ));
+        assert!(matches!(
+            parse(&["bin", "--stdin", "--example", "1"]),
+            Err(InputError::Conflicting)
+        ));
+    }
+}
@@ -0,0 +1,94 @@
+/// Keeps the `[[bin]]` entries of `Cargo.toml` in sync with solutions outside of `src/bin/*.rs`,
+/// which cargo does not discover by itself.
+use std::path::Path;
+use std::{fs, io};
+
+use crate::template::Day;
+
+static MANIFEST_PATH: &str = "Cargo.toml";
+
+/// Declares the solution of `day` at `path`. Returns `false` if it is already declared.
+pub fn register_bin(day: Day, path: &Path) -> Result<bool, io::Error> {
+    update_manifest(|manifest| with_bin(manifest, day, path))
+}
+
+/// Removes the declaration of the solution of `day`. Returns `false` if it is not declared.
+pub fn unregister_bin(day: Day) -> Result<bool, io::Error> {
+    update_manifest(|manifest| without_bin(manifest, day))
+}
+
+fn update_manifest(update: impl Fn(&str) -> Option<String>) -> Result<bool, io::Error> {
+    let manifest = fs::read_to_string(MANIFEST_PATH)?;
+    match update(&manifest) {
+        Some(manifest) => fs::write(MANIFEST_PATH, manifest).map(|()| true),
+        None => Ok(false),
+    }
+}
+
+fn name_line(day: Day) -> String {
+    format!("name = \"{day}\"")
+}
+
+fn with_bin(manifest: &str, day: Day, path: &Path) -> Option<String> {
+    if manifest.lines().any(|line| line.trim() == name_line(day)) {
+        return None;
+    }
+
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut manifest = manifest.trim_end().to_string();
+    manifest.push_str(&format!(
+        "\n\n[[bin]]\n{}\npath = \"{path}\"\n",
+        name_line(day)
+    ));
+    Some(manifest)
+}
+
+fn without_bin(manifest: &str, day: Day) -> Option<String> {
+    let lines: Vec<&str> = manifest.lines().collect();
+    let name = lines
+        .iter()
+        .position(|line| line.trim() == name_line(day))?;
+    let start = lines[..name]
+        .iter()
+        .rposition(|line| line.trim() == "[[bin]]")?;
+    let end = lines[name..]
+        .iter()
+        .position(|line| line.trim_start().starts_with('['))
+        .map_or(lines.len(), |offset| name + offset);
+
+    // drop the blank line in front of the entry as well.
+    let start = if start > 0 && lines[start - 1].trim().is_empty() {
+        start - 1
+    } else {
+        start
+    };
+
+    let mut kept = lines[..start].to_vec();
+    kept.extend(&lines[end..]);
+    Some(kept.join("\n") + "\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::Path;
+
+    use super::{with_bin, without_bin};
+    use crate::day;
+
+    #[test]
+    fn registers_and_removes_bins() {
+        let manifest = "[package]\nname = \"aoc\"\n\n[dependencies]\nitertools = \"0.13.0\"\n";
+        let path = Path::new("src/bin/2024/05.rs");
+
+        let registered = with_bin(manifest, day!(2024, 5), path).unwrap();
+        assert!(registered.ends_with(
+            "itertools = \"0.13.0\"\n\n[[bin]]\nname = \"2024-05\"\npath = \"src/bin/2024/05.rs\"\n"
+        ));
+        assert_eq!(with_bin(&registered, day!(2024, 5), path), None);
+
+        assert_eq!(without_bin(&registered, day!(2024, 5)).unwrap(), manifest);
+        assert_eq!(without_bin(manifest, day!(2024, 5)), None);
+    }
+}
@@ -1,68 +1,327 @@
-use std::{env, fs};
+use crate::template::config::config;
 
+pub mod answer;
 pub mod aoc_cli;
 pub mod commands;
+pub mod config;
+pub mod crosscheck;
+pub mod examples;
+pub mod input;
+pub mod params;
+pub mod playback;
 pub mod runner;
 
 pub use day::*;
 
 mod day;
+mod encryption;
+mod manifest;
 mod readme_benchmarks;
 mod run_multi;
 mod timings;
+mod years;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+/// Normalizes the text if `normalize_inputs` is set in `aoc.toml`.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let filepath = config().day_file(folder, day, ".txt");
+    let f = encryption::read_to_string(&filepath);
+    input::normalize_if_configured(f.expect("could not open input file"))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+/// Normalizes the text like [`read_file`].
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let filepath = config().day_file(folder, day, &format!("-{part}.txt"));
+    let f = encryption::read_to_string(&filepath);
+    input::normalize_if_configured(f.expect("could not open input file"))
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+/// In tests, every example in `data/examples/{day}.toml` is checked by one test per part whose
+/// answer it records, e.g. `examples::part_one::example_1`. `build.rs` lists these tests.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Days whose constants differ between examples and the real input pass their parameters type
+/// (see [`params!`](crate::params)) as `params: Params`. The parts then take `(&str, &Params)`,
+/// and receive the defaults overridden by the example metadata or by `--param key=value`.
+///
+/// Days can pass a validator after the parameters, e.g. `validate: validate_grid`. It takes the
+/// input and returns `Result<(), E>` with `E: Display`, and rejects a malformed input or example
+/// with its message before the parts run.
+///
+/// Days can pass a [`Crosscheck`](crate::template::crosscheck::Crosscheck) after the validator,
+/// e.g. `crosscheck: crosscheck`. `cargo crosscheck` then compares the parts with its slow
+/// reference implementations on random inputs, using the default parameters.
+///
+/// Alternative solutions of a part are listed last, e.g. `variants: [part_two::naive]` for a
+/// function `naive` in a module `part_two`. They are checked against the examples like the parts,
+/// and `cargo solve --variants` benchmarks them against the part, which stays the primary
+/// solution.
 #[macro_export]
 macro_rules! solution {
-    ($year:literal, $day:literal) => {
-        $crate::solution!(@impl $year, $day, [part_one, 1] [part_two, 2]);
+    (
+        $year:tt, $day:tt
+        $(, validate: $validate:ident)? $(, crosscheck: $crosscheck:ident)?
+        $(, variants: [$($vpart:ident::$vname:ident),* $(,)?])?
+    ) => {
+        $crate::solution!(
+            @impl $year, $day, [$($validate)?], [$($crosscheck)?], [$($($vpart::$vname),*)?],
+            [part_one, 1] [part_two, 2]
+        );
+    };
+    (
+        $year:tt, $day:tt, 1
+        $(, validate: $validate:ident)? $(, crosscheck: $crosscheck:ident)?
+        $(, variants: [$($vpart:ident::$vname:ident),* $(,)?])?
+    ) => {
+        $crate::solution!(
+            @impl $year, $day, [$($validate)?], [$($crosscheck)?], [$($($vpart::$vname),*)?],
+            [part_one, 1]
+        );
+    };
+    (
+        $year:tt, $day:tt, 2
+        $(, validate: $validate:ident)? $(, crosscheck: $crosscheck:ident)?
+        $(, variants: [$($vpart:ident::$vname:ident),* $(,)?])?
+    ) => {
+        $crate::solution!(
+            @impl $year, $day, [$($validate)?], [$($crosscheck)?], [$($($vpart::$vname),*)?],
+            [part_two, 2]
+        );
     };
-    ($year:literal, $day:literal, 1) => {
-        $crate::solution!(@impl $year, $day, [part_one, 1]);
+    (
+        $year:tt, $day:tt, params: $params:ty
+        $(, validate: $validate:ident)? $(, crosscheck: $crosscheck:ident)?
+        $(, variants: [$($vpart:ident::$vname:ident),* $(,)?])?
+    ) => {
+        $crate::solution!(
+            @impl_params $year, $day, $params, [$($validate)?], [$($crosscheck)?],
+            [$($($vpart::$vname),*)?], [part_one, 1] [part_two, 2]
+        );
     };
-    ($year:literal, $day:literal, 2) => {
-        $crate::solution!(@impl $year, $day, [part_two, 2]);
+    (
+        $year:tt, $day:tt, 1, params: $params:ty
+        $(, validate: $validate:ident)? $(, crosscheck: $crosscheck:ident)?
+        $(, variants: [$($vpart:ident::$vname:ident),* $(,)?])?
+    ) => {
+        $crate::solution!(
+            @impl_params $year, $day, $params, [$($validate)?], [$($crosscheck)?],
+            [$($($vpart::$vname),*)?], [part_one, 1]
+        );
+    };
+    (
+        $year:tt, $day:tt, 2, params: $params:ty
+        $(, validate: $validate:ident)? $(, crosscheck: $crosscheck:ident)?
+        $(, variants: [$($vpart:ident::$vname:ident),* $(,)?])?
+    ) => {
+        $crate::solution!(
+            @impl_params $year, $day, $params, [$($validate)?], [$($crosscheck)?],
+            [$($($vpart::$vname),*)?], [part_two, 2]
+        );
+    };
+
+    (
+        @impl $year:tt, $day:tt, [$($validate:ident)?], [$($crosscheck:ident)?],
+        [$($vpart:ident::$vname:ident),*], $( [$func:ident, $part:expr] )*
+    ) => {
+        $crate::solution!(@common $year, $day);
+
+        fn main() {
+            use $crate::template::runner::*;
+            $crate::template::crosscheck::run_if_requested(
+                DAY,
+                $crate::solution!(@option $($crosscheck)?),
+                &[$( ($part, |input: &str| $func(input).map(Into::into)) ),*],
+                $crate::solution!(@option $(|input: &str| $validate(input).is_ok())?),
+            );
+            let input = $crate::template::input::from_args(DAY);
+            $( $crate::template::input::validate_or_exit(DAY, &input, $validate); )?
+            $( run_part($func, input.text.as_str(), DAY, $part); )*
+            run_variants(
+                DAY,
+                input.text.as_str(),
+                &[
+                    $( ($part, stringify!($func), &|input: &str| $func(input).map(Into::into)), )*
+                    $(
+                        (
+                            $crate::solution!(@part $vpart),
+                            concat!(stringify!($vpart), "::", stringify!($vname)),
+                            &|input: &str| $vpart::$vname(input).map(Into::into),
+                        ),
+                    )*
+                ],
+            );
+        }
+
+        /// Checks every example in `data/examples/{DAY}.toml` that records an answer.
+        #[cfg(test)]
+        mod examples {
+            fn check_input(_input: &str) {
+                $( $crate::template::input::validate_example(_input, super::$validate); )?
+            }
+
+            $crate::solution!(
+                @example_tests $year, $day, [], [$($vpart::$vname)*], $( [$func, $part] )*
+            );
+        }
     };
 
-    (@impl $year:literal, $day:literal, $( [$func:expr, $part:expr] )*) => {
+    (
+        @impl_params $year:tt, $day:tt, $params:ty, [$($validate:ident)?],
+        [$($crosscheck:ident)?], [$($vpart:ident::$vname:ident),*], $( [$func:ident, $part:expr] )*
+    ) => {
+        $crate::solution!(@common $year, $day);
+
+        fn main() {
+            use $crate::template::runner::*;
+            // random inputs are solved with the default parameters.
+            $crate::template::crosscheck::run_if_requested(
+                DAY,
+                $crate::solution!(@option $($crosscheck)?),
+                &[$(
+                    (
+                        $part,
+                        |input: &str| $func(input, &<$params>::default()).map(Into::into),
+                    )
+                ),*],
+                $crate::solution!(@option $(|input: &str| $validate(input).is_ok())?),
+            );
+            let input = $crate::template::input::from_args(DAY);
+            $( $crate::template::input::validate_or_exit(DAY, &input, $validate); )?
+            let params: $params = $crate::template::params::from_args(input.example.as_ref());
+            $( run_part(|input| $func(input, &params), input.text.as_str(), DAY, $part); )*
+            run_variants(
+                DAY,
+                input.text.as_str(),
+                &[
+                    $(
+                        (
+                            $part,
+                            stringify!($func),
+                            &|input: &str| $func(input, &params).map(Into::into),
+                        ),
+                    )*
+                    $(
+                        (
+                            $crate::solution!(@part $vpart),
+                            concat!(stringify!($vpart), "::", stringify!($vname)),
+                            &|input: &str| $vpart::$vname(input, &params).map(Into::into),
+                        ),
+                    )*
+                ],
+            );
+        }
+
+        /// Checks every example in `data/examples/{DAY}.toml` that records an answer.
+        #[cfg(test)]
+        mod examples {
+            fn check_input(_input: &str) {
+                $( $crate::template::input::validate_example(_input, super::$validate); )?
+            }
+
+            $crate::solution!(
+                @example_tests $year, $day, [$params], [$($vpart::$vname)*],
+                $( [$func, $part] )*
+            );
+        }
+    };
+
+    // a module per part and variant, with a test per example that records an answer to the part.
+    (
+        @example_tests $year:tt, $day:tt, $params:tt, $variants:tt,
+        $( [$func:ident, $part:expr] )*
+    ) => {
+        include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+
+        $(
+            mod $func {
+                #[allow(dead_code)]
+                fn check(index: usize) {
+                    $crate::template::examples::check_example(
+                        super::super::DAY,
+                        $part,
+                        index,
+                        $crate::solution!(@solver $params, super::super::$func, super::check_input),
+                    );
+                }
+
+                example_tests!($year, $day, $func);
+                $crate::solution!(@variant_tests $year, $day, $params, $func, $part, $variants);
+            }
+        )*
+    };
+
+    (@variant_tests $year:tt, $day:tt, $params:tt, $func:ident, $part:tt, []) => {};
+    (
+        @variant_tests $year:tt, $day:tt, $params:tt, part_one, $part:tt,
+        [part_one::$vname:ident $($rest:tt)*]
+    ) => {
+        $crate::solution!(@variant_test $year, $day, $params, part_one, $part, $vname);
+        $crate::solution!(@variant_tests $year, $day, $params, part_one, $part, [$($rest)*]);
+    };
+    (
+        @variant_tests $year:tt, $day:tt, $params:tt, part_two, $part:tt,
+        [part_two::$vname:ident $($rest:tt)*]
+    ) => {
+        $crate::solution!(@variant_test $year, $day, $params, part_two, $part, $vname);
+        $crate::solution!(@variant_tests $year, $day, $params, part_two, $part, [$($rest)*]);
+    };
+    (
+        @variant_tests $year:tt, $day:tt, $params:tt, $func:ident, $part:tt,
+        [$vpart:ident::$vname:ident $($rest:tt)*]
+    ) => {
+        $crate::solution!(@variant_tests $year, $day, $params, $func, $part, [$($rest)*]);
+    };
+    (@variant_test $year:tt, $day:tt, $params:tt, $func:ident, $part:tt, $vname:ident) => {
+        mod $vname {
+            #[allow(dead_code)]
+            fn check(index: usize) {
+                $crate::template::examples::check_example(
+                    super::super::super::DAY,
+                    $part,
+                    index,
+                    $crate::solution!(@solver $params, super::super::super::$func::$vname),
+                );
+            }
+
+            example_tests!($year, $day, $func);
+        }
+    };
+
+    // the parameters are inferred from the solution, as their type is not in scope of the tests.
+    (@solver [], $func:path $(, $validate:path)?) => {
+        |input: &str, (): &()| {
+            $( $validate(input); )?
+            $func(input)
+        }
+    };
+    (@solver [$params:ty], $func:path $(, $validate:path)?) => {
+        |input, params| {
+            $( $validate(input); )?
+            $func(input, params)
+        }
+    };
+
+    (@part part_one) => { 1 };
+    (@part part_two) => { 2 };
+
+    (@option) => { None };
+    (@option $value:expr) => { Some($value) };
+
+    (@common $year:tt, $day:tt) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($year, $day);
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
-
-        fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
-        }
     };
 }
@@ -0,0 +1,199 @@
+/// Per-day puzzle parameters, i.e. constants that differ between the examples and the real input.
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::{env, process};
+
+use crate::template::examples::Example;
+
+/// A set of named parameters whose [`Default`] holds the values for the real input.
+///
+/// Usually implemented with the [`params!`](crate::params) macro.
+pub trait Params: Clone + Default {
+    /// Overrides the parameter `key` with a value parsed from `value`.
+    fn set(&mut self, key: &str, value: &str) -> Result<(), ParamsError>;
+
+    /// Returns the defaults with every `(key, value)` override applied in order.
+    fn with_overrides<K: AsRef<str>, V: AsRef<str>>(
+        overrides: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<Self, ParamsError> {
+        let mut params = Self::default();
+        for (key, value) in overrides {
+            params.set(key.as_ref(), value.as_ref())?;
+        }
+        Ok(params)
+    }
+}
+
+/// Days without parameters. Every override is rejected.
+impl Params for () {
+    fn set(&mut self, key: &str, _: &str) -> Result<(), ParamsError> {
+        Err(ParamsError::UnknownKey(key.to_string()))
+    }
+}
+
+/// Parses the value of parameter `key`. Used by the [`params!`](crate::params) macro.
+pub fn parse_value<T: FromStr>(key: &str, value: &str) -> Result<T, ParamsError>
+where
+    T::Err: Display,
+{
+    value
+        .trim()
+        .parse()
+        .map_err(|e: T::Err| ParamsError::InvalidValue {
+            key: key.to_string(),
+            value: value.to_string(),
+            message: e.to_string(),
+        })
+}
+
+/// Splits a `key=value` override.
+pub fn parse_override(arg: &str) -> Result<(&str, &str), ParamsError> {
+    arg.split_once('=')
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .ok_or_else(|| ParamsError::Malformed(arg.to_string()))
+}
+
+/// Reads the parameters passed to the solution as `--param key=value`, exits on invalid input.
+///
+/// The overrides apply on top of the parameters of `example`, if the solution runs one.
+pub fn from_args<P: Params>(example: Option<&Example>) -> P {
+    let args: Vec<String> = env::args().collect();
+    let params = example.map_or_else(|| Ok(P::default()), Example::params::<P>);
+    let params = params.and_then(|mut params| {
+        for pair in args.windows(2).filter(|pair| pair[0] == "--param") {
+            let (key, value) = parse_override(&pair[1])?;
+            params.set(key, value)?;
+        }
+        Ok(params)
+    });
+
+    match params {
+        Ok(params) => params,
+        Err(e) => {
+            eprintln!("Invalid parameter: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Declares a parameters struct with defaults and implements [`Params`] for it.
+///
+/// ```
+/// advent_of_code::params! {
+///     pub struct Params {
+///         /// Number of blinks in part one.
+///         blinks: u32 = 25,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! params {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $( $(#[$field_meta:meta])* $field:ident : $ty:ty = $default:expr ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, PartialEq)]
+        $vis struct $name {
+            $( $(#[$field_meta])* pub $field: $ty, )*
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self {
+                    $( $field: $default, )*
+                }
+            }
+        }
+
+        impl $crate::template::params::Params for $name {
+            fn set(
+                &mut self,
+                key: &str,
+                value: &str,
+            ) -> Result<(), $crate::template::params::ParamsError> {
+                match key {
+                    $( stringify!($field) => {
+                        self.$field = $crate::template::params::parse_value(key, value)?;
+                    } )*
+                    _ => {
+                        return Err($crate::template::params::ParamsError::UnknownKey(
+                            key.to_string(),
+                        ))
+                    }
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+/// An error which can be returned when overriding parameters.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParamsError {
+    UnknownKey(String),
+    InvalidValue {
+        key: String,
+        value: String,
+        message: String,
+    },
+    Malformed(String),
+}
+
+impl Error for ParamsError {}
+
+impl Display for ParamsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParamsError::UnknownKey(key) => write!(f, "unknown parameter \"{key}\"."),
+            ParamsError::InvalidValue {
+                key,
+                value,
+                message,
+            } => write!(f, "invalid value \"{value}\" for \"{key}\": {message}."),
+            ParamsError::Malformed(arg) => {
+                write!(f, "expecting \"key=value\", found \"{arg}\".")
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_override, Params, ParamsError};
+
+    crate::params! {
+        struct Mock {
+            steps: u32 = 64,
+            label: String = "real".to_string(),
+        }
+    }
+
+    #[test]
+    fn applies_overrides() {
+        assert_eq!(Mock::default().steps, 64);
+        let params = Mock::with_overrides([("steps", "6"), ("label", "example")]).unwrap();
+        assert_eq!(params.steps, 6);
+        assert_eq!(params.label, "example");
+    }
+
+    #[test]
+    fn rejects_invalid_overrides() {
+        assert_eq!(
+            Mock::with_overrides([("size", "1")]),
+            Err(ParamsError::UnknownKey("size".into()))
+        );
+        assert!(matches!(
+            Mock::with_overrides([("steps", "-1")]),
+            Err(ParamsError::InvalidValue { .. })
+        ));
+        assert_eq!(parse_override("steps = 6"), Ok(("steps", "6")));
+        assert!(parse_override("steps").is_err());
+        assert!(<()>::with_overrides([("steps", "6")]).is_err());
+    }
+}
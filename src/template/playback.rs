@@ -0,0 +1,328 @@
+/// Plays the frames recorded with [`crate::visualize`] in the terminal, or exports them as images.
+use std::error::Error;
+use std::fmt::Display;
+use std::io::{self, stdout, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+use std::{env, fs, process, thread};
+
+use crate::template::Day;
+use crate::visualize::{self, Frame};
+
+/// The size of a cell in exported images, in pixels.
+const CELL_SIZE: usize = 4;
+
+/// The default speed of the terminal animation, in frames per second.
+pub const DEFAULT_FPS: u32 = 20;
+
+/// How recorded frames are shown, selected with `--visualize`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Playback {
+    /// Frames per second of the terminal animation, `0` advances on enter.
+    pub fps: u32,
+    /// The folder to export the frames to instead of playing them.
+    pub export: Option<PathBuf>,
+    pub format: ImageFormat,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ImageFormat {
+    #[default]
+    Ppm,
+    Png,
+}
+
+impl ImageFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Ppm => "ppm",
+            ImageFormat::Png => "png",
+        }
+    }
+
+    fn encode(self, width: usize, height: usize, pixels: &[u8]) -> Vec<u8> {
+        match self {
+            ImageFormat::Ppm => encode_ppm(width, height, pixels),
+            ImageFormat::Png => encode_png(width, height, pixels),
+        }
+    }
+}
+
+impl FromStr for ImageFormat {
+    type Err = PlaybackError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ppm" => Ok(ImageFormat::Ppm),
+            "png" => Ok(ImageFormat::Png),
+            _ => Err(PlaybackError::InvalidFormat(s.to_string())),
+        }
+    }
+}
+
+impl Display for ImageFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.extension())
+    }
+}
+
+impl Playback {
+    /// Reads the playback from the arguments passed to the solution:
+    /// `--visualize [--fps <n>] [--export <dir>] [--format <ppm|png>]`. Returns [`None`] without
+    /// `--visualize`.
+    pub fn from_args(args: &[String]) -> Result<Option<Self>, PlaybackError> {
+        if !args.iter().any(|arg| arg == "--visualize") {
+            return Ok(None);
+        }
+        let value_of = |flag: &str| {
+            args.iter()
+                .position(|arg| arg == flag)
+                .map(|i| args.get(i + 1).cloned().unwrap_or_default())
+        };
+
+        let fps = match value_of("--fps") {
+            Some(fps) => fps.parse().map_err(|_| PlaybackError::InvalidFps(fps))?,
+            None => DEFAULT_FPS,
+        };
+        let format = match value_of("--format") {
+            Some(format) => format.parse()?,
+            None => ImageFormat::default(),
+        };
+
+        Ok(Some(Self {
+            fps,
+            export: value_of("--export").map(PathBuf::from),
+            format,
+        }))
+    }
+
+    /// The arguments that select this playback when passed to a solution.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec!["--visualize".into(), "--fps".into(), self.fps.to_string()];
+        if let Some(export) = &self.export {
+            args.extend(["--export".into(), export.display().to_string()]);
+            args.extend(["--format".into(), self.format.to_string()]);
+        }
+        args
+    }
+}
+
+/// Shows the frames recorded while solving `part` as selected by the arguments, exits on
+/// invalid arguments.
+pub fn present(day: Day, part: u8) {
+    let frames = visualize::take_frames();
+    if frames.is_empty() {
+        return;
+    }
+
+    let args: Vec<String> = env::args().collect();
+    let playback = match Playback::from_args(&args) {
+        Ok(Some(playback)) => playback,
+        Ok(None) => return,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    match &playback.export {
+        Some(dir) => match export(&frames, dir, &format!("{day}-part{part}"), playback.format) {
+            Ok(()) => println!(
+                "Exported {} frame(s) of part {part} to \"{}\".",
+                frames.len(),
+                dir.display()
+            ),
+            Err(e) => {
+                eprintln!("Failed to export the frames: {e}");
+                process::exit(1);
+            }
+        },
+        None => play(&frames, playback.fps),
+    }
+}
+
+/// Animates `frames` in place below the output of the solution.
+fn play(frames: &[Frame], fps: u32) {
+    let mut stdout = stdout();
+    let mut lines = 0;
+    for (i, frame) in frames.iter().enumerate() {
+        if lines > 0 {
+            // move up to the previous frame and clear it.
+            print!("\x1b[{lines}A\x1b[J");
+        }
+        let text = frame.render_text();
+        print!("{text}");
+        println!("Frame {}/{}", i + 1, frames.len());
+        let _ = stdout.flush();
+        lines = text.lines().count() + 1;
+
+        if fps == 0 {
+            if i + 1 < frames.len() {
+                let _ = io::stdin().lock().read_line(&mut String::new());
+                lines += 1;
+            }
+        } else {
+            thread::sleep(Duration::from_secs_f64(1.0 / f64::from(fps)));
+        }
+    }
+}
+
+/// Writes every frame to a numbered image named after `prefix`, e.g. `2024-10-part1-00001.ppm`.
+fn export(frames: &[Frame], dir: &Path, prefix: &str, format: ImageFormat) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    for (i, frame) in frames.iter().enumerate() {
+        let (width, height, pixels) = frame.render_pixels(CELL_SIZE);
+        let path = dir.join(format!("{prefix}-{:05}.{}", i + 1, format.extension()));
+        fs::write(path, format.encode(width, height, &pixels))?;
+    }
+    Ok(())
+}
+
+/// Encodes RGB pixels as a binary PPM image.
+fn encode_ppm(width: usize, height: usize, pixels: &[u8]) -> Vec<u8> {
+    [format!("P6\n{width} {height}\n255\n").as_bytes(), pixels].concat()
+}
+
+/// Encodes RGB pixels as an uncompressed PNG image.
+fn encode_png(width: usize, height: usize, pixels: &[u8]) -> Vec<u8> {
+    let mut header = vec![];
+    header.extend((width as u32).to_be_bytes());
+    header.extend((height as u32).to_be_bytes());
+    // 8 bits per channel, RGB, default compression, filtering and no interlacing.
+    header.extend([8, 2, 0, 0, 0]);
+
+    // every row starts with its filter type, which is none.
+    let rows: Vec<u8> = pixels
+        .chunks(width * 3)
+        .flat_map(|row| [&[0][..], row].concat())
+        .collect();
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    write_chunk(&mut png, b"IHDR", &header);
+    write_chunk(&mut png, b"IDAT", &zlib_stored(&rows));
+    write_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend((data.len() as u32).to_be_bytes());
+    png.extend(kind);
+    png.extend(data);
+    png.extend(crc32(&[&kind[..], data].concat()).to_be_bytes());
+}
+
+/// Wraps `data` in a zlib stream of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut stream = vec![0x78, 0x01];
+    let mut blocks = data.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        stream.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        stream.push(u8::from(blocks.peek().is_none()));
+        let len = block.len() as u16;
+        stream.extend(len.to_le_bytes());
+        stream.extend((!len).to_le_bytes());
+        stream.extend(block);
+    }
+    stream.extend(adler32(data).to_be_bytes());
+    stream
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = u32::MAX;
+    for byte in data {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in data {
+        a = (a + u32::from(*byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+#[derive(Debug)]
+pub enum PlaybackError {
+    InvalidFps(String),
+    InvalidFormat(String),
+}
+
+impl Error for PlaybackError {}
+
+impl Display for PlaybackError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlaybackError::InvalidFps(fps) => {
+                write!(f, "Invalid frame rate \"{fps}\", expecting a number.")
+            }
+            PlaybackError::InvalidFormat(format) => {
+                write!(
+                    f,
+                    "Invalid image format \"{format}\", expecting ppm or png."
+                )
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{adler32, crc32, encode_png, encode_ppm, ImageFormat, Playback};
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn parses_playback() {
+        assert_eq!(Playback::from_args(&args(&["--fps", "5"])).unwrap(), None);
+
+        let playback = Playback::from_args(&args(&["--visualize", "--export", "out"]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(playback.format, ImageFormat::Ppm);
+        assert_eq!(
+            Playback::from_args(&playback.to_args()).unwrap(),
+            Some(playback)
+        );
+
+        assert!(Playback::from_args(&args(&["--visualize", "--fps", "fast"])).is_err());
+        assert!(Playback::from_args(&args(&["--visualize", "--format", "gif"])).is_err());
+    }
+
+    #[test]
+    fn computes_checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn encodes_images() {
+        let pixels = [255, 0, 0, 0, 0, 255];
+        assert_eq!(
+            encode_ppm(2, 1, &pixels),
+            b"P6\n2 1\n255\n\xff\x00\x00\x00\x00\xff"
+        );
+
+        let png = encode_png(2, 1, &pixels);
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n\x00\x00\x00\x0dIHDR"));
+        assert!(png.ends_with(b"IEND\xae\x42\x60\x82"));
+        // the filter byte and both pixels are stored uncompressed.
+        assert!(png.windows(7).any(|w| w == [0, 255, 0, 0, 0, 0, 255]));
+    }
+}
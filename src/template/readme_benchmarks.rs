@@ -1,12 +1,21 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+///
+/// Years with their own markers (see [`year_marker`]) get their own table, the timings of all
+/// other years go into the table between the plain markers.
+use std::{collections::BTreeSet, fs, io};
 
+use crate::template::config::config;
 use crate::template::timings::Timings;
 use crate::template::Day;
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
+/// The marker of the benchmark table of `year`.
+pub fn year_marker(year: u16) -> String {
+    format!("<!--- benchmarking table {year} --->")
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -27,11 +36,11 @@ pub struct TablePosition {
 
 #[must_use]
 pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+    format!("./{}", config().bin_path(day).display())
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,11 +61,20 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn construct_table(
+    prefix: &str,
+    marker: &str,
+    year: Option<u16>,
+    timings: Timings,
+    total_millis: f64,
+) -> String {
+    let header = match year {
+        Some(year) => format!("{prefix} {year} Benchmarks"),
+        None => format!("{prefix} Benchmarks"),
+    };
 
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
+        marker.into(),
         header,
         String::new(),
         "| Day | Part 1 | Part 2 |".into(),
@@ -76,20 +94,109 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn replace_table(
+    s: &mut String,
+    marker: &str,
+    year: Option<u16>,
+    timings: Timings,
+    total_millis: f64,
+) -> Result<(), Error> {
+    let positions = locate_table(s, marker)?;
+    let table = construct_table("##", marker, year, timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
+fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
+    let years: BTreeSet<u16> = timings.data.iter().map(|t| t.day.into_inner().0).collect();
+    let (own_table, shared_table): (BTreeSet<u16>, BTreeSet<u16>) = years
+        .into_iter()
+        .partition(|year| s.contains(&year_marker(*year)));
+
+    if own_table.is_empty() {
+        return replace_table(s, MARKER, None, timings, total_millis);
+    }
+
+    for year in &own_table {
+        let data = timings
+            .data
+            .iter()
+            .filter(|t| t.day.into_inner().0 == *year)
+            .cloned()
+            .collect();
+        let year_timings = Timings { data };
+        let year_millis = year_timings.total_millis();
+        replace_table(
+            s,
+            &year_marker(*year),
+            Some(*year),
+            year_timings,
+            year_millis,
+        )?;
+    }
+
+    if !shared_table.is_empty() {
+        let data = timings
+            .data
+            .into_iter()
+            .filter(|t| shared_table.contains(&t.day.into_inner().0))
+            .collect();
+        let shared_timings = Timings { data };
+        let shared_millis = shared_timings.total_millis();
+        replace_table(s, MARKER, None, shared_timings, shared_millis)?;
+    } else if s.contains(MARKER) {
+        // every year moved to its own table, e.g. after `new-year`.
+        let positions = locate_table(s, MARKER)?;
+        s.replace_range(
+            positions.pos_start..positions.pos_end,
+            &empty_table(MARKER, "## Benchmarks"),
+        );
+    }
+
+    Ok(())
+}
+
+fn empty_table(marker: &str, header: &str) -> String {
+    format!("{marker}\n{header}\n\n_Not benchmarked yet._\n{marker}")
+}
+
+/// Inserts an empty benchmark table for `year` before the other tables.
+/// Returns `false` if the readme already has one.
+fn insert_year_table(s: &mut String, year: u16) -> bool {
+    let marker = year_marker(year);
+    if s.contains(&marker) {
+        return false;
+    }
+
+    let table = empty_table(&marker, &format!("## {year} Benchmarks")) + "\n\n";
+    let position = s
+        .match_indices("<!--- benchmarking table")
+        .map(|(position, _)| position)
+        .next()
+        .unwrap_or(s.len());
+    s.insert_str(position, &table);
+    true
+}
+
+/// Adds an empty benchmark table for `year` to the readme.
+/// Returns `false` if the readme already has one.
+pub fn add_year_table(year: u16) -> Result<bool, Error> {
+    let path = &config().readme;
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    let inserted = insert_year_table(&mut readme, year);
+    if inserted {
+        fs::write(path, &readme)?;
+    }
+    Ok(inserted)
+}
+
 pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
+    let path = &config().readme;
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
     update_content(&mut readme, timings, total_millis)?;
@@ -99,26 +206,26 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
+    use super::{insert_year_table, update_content, year_marker, MARKER};
     use crate::{day, template::timings::Timing, template::timings::Timings};
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
+                    day: day!(2024, 1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
                 },
                 Timing {
-                    day: day!(2),
+                    day: day!(2024, 2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
                 },
                 Timing {
-                    day: day!(4),
+                    day: day!(2024, 4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
@@ -169,9 +276,9 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 2024-01](./src/bin/2024-01.rs) | `10ms` | `20ms` |",
+            "| [Day 2024-02](./src/bin/2024-02.rs) | `30ms` | `40ms` |",
+            "| [Day 2024-04](./src/bin/2024-04.rs) | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +287,68 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn updates_tables_per_year() {
+        let mut s = format!("{0}\n{0}\n{1}\n{1}", year_marker(2024), MARKER);
+        let timings = Timings {
+            data: vec![
+                Timing {
+                    day: day!(2023, 1),
+                    part_1: Some("1ms".into()),
+                    part_2: None,
+                    total_nanos: 1e+6,
+                },
+                Timing {
+                    day: day!(2024, 1),
+                    part_1: Some("2ms".into()),
+                    part_2: None,
+                    total_nanos: 2e+6,
+                },
+            ],
+        };
+        update_content(&mut s, timings, 3.0).unwrap();
+        let (year_table, shared_table) = s.split_once(MARKER).unwrap();
+        assert!(year_table.contains("## 2024 Benchmarks"));
+        assert!(year_table.contains("2024-01"));
+        assert!(year_table.contains("**Total: 2.00ms**"));
+        assert!(!year_table.contains("2023-01"));
+        assert!(shared_table.contains("2023-01"));
+        assert!(shared_table.contains("**Total: 1.00ms**"));
+
+        let timings = Timings {
+            data: vec![Timing {
+                day: day!(2024, 1),
+                part_1: Some("3ms".into()),
+                part_2: None,
+                total_nanos: 3e+6,
+            }],
+        };
+        let mut s = format!(
+            "{0}\n{0}\n{1}\n| [Day 2024-01](x) | `1ms` | - |\n{1}",
+            year_marker(2024),
+            MARKER
+        );
+        update_content(&mut s, timings, 3.0).unwrap();
+        let (year_table, shared_table) = s.split_once(MARKER).unwrap();
+        assert!(year_table.contains("`3ms`"));
+        assert!(!shared_table.contains("2024-01"));
+        assert!(shared_table.contains("_Not benchmarked yet._"));
+
+        // a readme with only tables per year.
+        let mut s = format!("{0}\n{0}", year_marker(2024));
+        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        assert!(!s.contains(MARKER));
+    }
+
+    #[test]
+    fn inserts_year_tables() {
+        let mut s = format!("# readme\n{MARKER}\n");
+        assert!(insert_year_table(&mut s, 2024));
+        assert!(insert_year_table(&mut s, 2025));
+        assert!(!insert_year_table(&mut s, 2025));
+        let position = |marker: &str| s.find(marker).unwrap();
+        assert!(position(&year_marker(2025)) < position(&year_marker(2024)));
+        assert!(position(&year_marker(2024)) < position(MARKER));
+    }
 }
@@ -6,19 +6,134 @@ use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::ocr;
+use crate::template::answer::Answer;
+use crate::template::config::config;
+use crate::template::input::InputSource;
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, playback, Day, ANSI_ITALIC, ANSI_RESET};
+
+/// Printed before an answer is submitted.
+pub const SUBMITTING: &str = "Submitting result via aoc-cli...";
+
+pub fn run_part<I: Clone, T: Into<Answer>>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: u8,
+) {
+    if selected_part().is_some_and(|selected| selected != part) {
+        return;
+    }
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
+    let func = |input| func(input).map(Into::into);
 
     let (result, duration, samples) =
         run_timed(func, input, |result| print_result(result, &part_str, ""));
 
     print_result(&result, &part_str, &format_duration(&duration, samples));
+    playback::present(day, part);
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if let Some(result) = result
+        && let Some(Err(e)) = submit_result(result.submission(), day, part)
+    {
+        eprintln!("Failed to submit the answer: {e}");
+        process::exit(1);
+    }
+}
+
+/// A named solution of one part, with its answer normalized.
+pub type Variant<'a> = (u8, &'static str, &'a dyn Fn(&str) -> Option<Answer>);
+
+/// Benchmarks the variants of every part against each other if `--variants` is passed. The first
+/// variant of a part is the primary solution, whose answer the others should match. Exits with an
+/// error if a variant disagrees.
+pub fn run_variants(day: Day, input: &str, variants: &[Variant]) {
+    if !env::args().any(|x| x == "--variants") {
+        return;
+    }
+
+    let mut parts: Vec<u8> = variants.iter().map(|(part, ..)| *part).collect();
+    parts.sort_unstable();
+    parts.dedup();
+    parts.retain(|part| selected_part().is_none_or(|selected| selected == *part));
+
+    let mut compared = false;
+    let mut agree = true;
+    for part in parts {
+        let variants: Vec<&Variant> = variants.iter().filter(|(p, ..)| *p == part).collect();
+        if variants.len() < 2 {
+            continue;
+        }
+        compared = true;
+
+        println!();
+        println!("{ANSI_BOLD}Part {part} variants{ANSI_RESET}");
+        let width = variants
+            .iter()
+            .map(|(_, name, _)| name.len())
+            .max()
+            .unwrap_or(0);
+
+        let mut primary: Option<(Option<Answer>, Duration)> = None;
+        for (_, name, func) in variants {
+            print!("{name:<width$}  ");
+            let _ = stdout().flush();
+
+            let timer = Instant::now();
+            let result = func(input);
+            let (duration, samples) = bench(func, input, &timer.elapsed());
+
+            let comparison = match &primary {
+                None => String::new(),
+                Some((expected, _)) if *expected != result => {
+                    agree = false;
+                    "  ✗ differs from the primary solution".to_string()
+                }
+                Some((_, primary)) => format_speedup(primary, &duration),
+            };
+            let answer = result.as_ref().map_or("✖".to_string(), Answer::submission);
+            print!("\r");
+            println!(
+                "{name:<width$}  {ANSI_BOLD}{answer}{ANSI_RESET}{}{comparison}",
+                format_duration(&duration, samples)
+            );
+
+            primary.get_or_insert((result, duration));
+        }
+    }
+
+    if !compared {
+        println!("{day} has no variants. Pass e.g. `variants: [part_two::naive]` to `solution!`.");
+    }
+    if !agree {
+        eprintln!("The variants of {day} disagree.");
+        process::exit(1);
+    }
+}
+
+/// How much faster or slower a variant is than the primary solution.
+fn format_speedup(primary: &Duration, variant: &Duration) -> String {
+    let ratio = variant.as_secs_f64() / primary.as_secs_f64().max(f64::MIN_POSITIVE);
+    if ratio >= 1.0 {
+        format!("  {ratio:.2}x slower")
+    } else {
+        format!("  {:.2}x faster", 1.0 / ratio.max(f64::MIN_POSITIVE))
+    }
+}
+
+/// Parse the `--part` argument passed to `solve` or `time`, which restricts the run to one part.
+fn selected_part() -> Option<u8> {
+    let args: Vec<String> = env::args().collect();
+    let part_index = args.iter().position(|x| x == "--part")? + 1;
+
+    match args.get(part_index).map(|x| x.parse::<u8>()) {
+        Some(Ok(part @ (1 | 2))) => Some(part),
+        _ => {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 --part 2");
+            process::exit(1);
+        }
     }
 }
 
@@ -58,8 +173,9 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let settings = &config().bench;
+    let bench_iterations = (settings.target_time.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+        .clamp(settings.min_samples, settings.max_samples);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -94,13 +210,19 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(result: &Option<Answer>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
         Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
+            let result = result.to_string();
+            if result.contains('\n') {
+                let str = match ocr::recognize(&result) {
+                    Ok(text) if !is_intermediate_result => {
+                        format!("{part}: {ANSI_BOLD}{text}{ANSI_RESET} ▼{duration_str}")
+                    }
+                    _ => format!("{part}: ▼ {duration_str}"),
+                };
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -159,11 +281,16 @@ fn submit_result<T: Display>(
         return None;
     }
 
+    if !matches!(InputSource::from_args(&args), Ok(InputSource::Puzzle)) {
+        eprintln!("--submit can only be used with the puzzle input.");
+        process::exit(1);
+    }
+
     if aoc_cli::check().is_err() {
         eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
+    println!("{SUBMITTING}");
     Some(aoc_cli::submit(day, part, &result.to_string()))
 }
@@ -1,10 +1,10 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
+use crate::template::config::config;
+use crate::template::run_multi::child_commands::parse_duration;
 use crate::template::Day;
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
-
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
@@ -14,6 +14,31 @@ pub struct Timing {
     pub total_nanos: f64,
 }
 
+impl Timing {
+    /// If `self` only measured `part`, combines it with the timing of the other part from
+    /// `stored`. A run of both parts replaces `stored`.
+    fn merged_with(&self, stored: &Timing, part: Option<u8>) -> Timing {
+        let (part_1, part_2) = match part {
+            Some(1) => (self.part_1.clone(), stored.part_2.clone()),
+            Some(2) => (stored.part_1.clone(), self.part_2.clone()),
+            _ => return self.clone(),
+        };
+
+        let total_nanos = [&part_1, &part_2]
+            .into_iter()
+            .flatten()
+            .filter_map(|timing| parse_duration(timing))
+            .sum();
+
+        Timing {
+            day: self.day,
+            part_1,
+            part_2,
+            total_nanos,
+        }
+    }
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
@@ -25,13 +50,13 @@ impl Timings {
     /// Dehydrate timings to a JSON file.
     pub fn store_file(&self) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(&config().timings_file)?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
     pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
+        let s = fs::read_to_string(&config().timings_file)
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from);
 
@@ -45,11 +70,16 @@ impl Timings {
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
-    pub fn merge(&self, new: &Self) -> Self {
+    /// If `other` only ran `part` of a day, the stored timing of the other part is kept.
+    pub fn merge(&self, new: &Self, part: Option<u8>) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let stored = self.data.iter().find(|t| t.day == timing.day);
+            data.push(match stored {
+                Some(stored) => timing.merged_with(stored, part),
+                None => timing.clone(),
+            });
         }
 
         for timing in &self.data {
@@ -194,19 +224,19 @@ mod tests {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
+                    day: day!(2024, 1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
                 },
                 Timing {
-                    day: day!(2),
+                    day: day!(2024, 2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
                 },
                 Timing {
-                    day: day!(4),
+                    day: day!(2024, 4),
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
@@ -220,11 +250,11 @@ mod tests {
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "data": [{ "day": "2024-01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
-            assert_eq!(timing.day, day!(1));
+            assert_eq!(timing.day, day!(2024, 1));
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
@@ -247,7 +277,7 @@ mod tests {
         #[test]
         #[should_panic]
         fn panics_for_malformed_timings() {
-            let json = r#"{ "data": [{ "day": "01" }, { "day": "26" }, { "day": "02", "part_2": null, "total_nanos": 0 }] }"#.to_string();
+            let json = r#"{ "data": [{ "day": "2024-01" }, { "day": "2024-26" }, { "day": "2024-02", "part_2": null, "total_nanos": 0 }] }"#.to_string();
             Timings::try_from(json).unwrap();
         }
     }
@@ -285,42 +315,42 @@ mod tests {
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
+                    day: day!(2024, 1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(2024, 1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
+                    day: day!(2024, 1),
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(2024, 1)), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
+                    day: day!(2024, 1),
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(2024, 1)), false);
         }
     }
 
@@ -337,18 +367,18 @@ mod tests {
             let timings = get_mock_timings();
             let other = Timings {
                 data: vec![Timing {
-                    day: day!(3),
+                    day: day!(2024, 3),
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
                 }],
             };
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other, None);
             assert_eq!(merged.data.len(), 4);
-            assert_eq!(merged.data[0].day, day!(1));
-            assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[2].day, day!(3));
-            assert_eq!(merged.data[3].day, day!(4));
+            assert_eq!(merged.data[0].day, day!(2024, 1));
+            assert_eq!(merged.data[1].day, day!(2024, 2));
+            assert_eq!(merged.data[2].day, day!(2024, 3));
+            assert_eq!(merged.data[3].day, day!(2024, 4));
         }
 
         #[test]
@@ -357,26 +387,75 @@ mod tests {
 
             let other = Timings {
                 data: vec![Timing {
-                    day: day!(2),
+                    day: day!(2024, 2),
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
                 }],
             };
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other, None);
 
             assert_eq!(merged.data.len(), 3);
-            assert_eq!(merged.data[0].day, day!(1));
-            assert_eq!(merged.data[1].day, day!(2));
+            assert_eq!(merged.data[0].day, day!(2024, 1));
+            assert_eq!(merged.data[1].day, day!(2024, 2));
             assert_eq!(merged.data[1].total_nanos, 0_f64);
-            assert_eq!(merged.data[2].day, day!(4));
+            assert_eq!(merged.data[2].day, day!(2024, 4));
+        }
+
+        #[test]
+        fn handles_partial_timings() {
+            let timings = get_mock_timings();
+
+            let other = Timings {
+                data: vec![
+                    Timing {
+                        day: day!(2024, 2),
+                        part_1: None,
+                        part_2: Some("5ms".into()),
+                        total_nanos: 5e+6,
+                    },
+                    Timing {
+                        day: day!(2024, 3),
+                        part_1: None,
+                        part_2: Some("1ms".into()),
+                        total_nanos: 1e+6,
+                    },
+                ],
+            };
+            let merged = timings.merge(&other, Some(2));
+
+            assert_eq!(merged.data.len(), 4);
+            assert_eq!(merged.data[1].part_1, Some("30ms".into()));
+            assert_eq!(merged.data[1].part_2, Some("5ms".into()));
+            assert_eq!(merged.data[1].total_nanos, 3.5e+7);
+            assert_eq!(merged.data[2].part_1, None);
+            assert_eq!(merged.data[2].part_2, Some("1ms".into()));
+        }
+
+        #[test]
+        fn replaces_parts_that_were_run() {
+            let timings = get_mock_timings();
+
+            let other = Timings {
+                data: vec![Timing {
+                    day: day!(2024, 1),
+                    part_1: Some("5ms".into()),
+                    part_2: None,
+                    total_nanos: 5e+6,
+                }],
+            };
+            let merged = timings.merge(&other, None);
+
+            assert_eq!(merged.data[0].part_1, Some("5ms".into()));
+            assert_eq!(merged.data[0].part_2, None);
+            assert_eq!(merged.data[0].total_nanos, 5e+6);
         }
 
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
             let other = get_mock_timings();
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other, None);
             assert_eq!(merged.data.len(), 3);
         }
 
@@ -384,7 +463,7 @@ mod tests {
         fn handles_empty_other_timings() {
             let timings = get_mock_timings();
             let other = Timings::default();
-            let merged = timings.merge(&other);
+            let merged = timings.merge(&other, None);
             assert_eq!(merged.data.len(), 3);
         }
     }
@@ -0,0 +1,146 @@
+/// Creates the modules in `src/years` that the solutions of one year share.
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+static LIB_PATH: &str = "src/lib.rs";
+static YEARS_DIR: &str = "src/years";
+
+const YEARS_MODULE_TEMPLATE: &str = "\
+//! Modules shared by the solutions of one year, e.g. the Intcode computer of 2019.
+";
+
+/// The name of the module of `year`, e.g. `y2019`.
+pub fn module_name(year: u16) -> String {
+    format!("y{year}")
+}
+
+pub fn module_path(year: u16) -> PathBuf {
+    PathBuf::from(YEARS_DIR).join(format!("{}.rs", module_name(year)))
+}
+
+/// The import that brings the module of `year` into scope of a solution.
+pub fn import(year: u16) -> String {
+    format!("use advent_of_code::years::{};", module_name(year))
+}
+
+/// Creates the module of `year` and declares it in the library. Returns `false` if it already
+/// exists.
+pub fn create(year: u16) -> Result<bool, io::Error> {
+    let path = module_path(year);
+    fs::create_dir_all(YEARS_DIR)?;
+    match OpenOptions::new().write(true).create_new(true).open(&path) {
+        Ok(mut file) => file.write_all(
+            format!(
+                "//! Shared by the solutions of {year}, which import it with `{}`.\n",
+                import(year)
+            )
+            .as_bytes(),
+        )?,
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => return Ok(false),
+        Err(e) => return Err(e),
+    }
+
+    let years_path = PathBuf::from(YEARS_DIR).join("mod.rs");
+    let years = match fs::read_to_string(&years_path) {
+        Ok(years) => years,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => YEARS_MODULE_TEMPLATE.to_string(),
+        Err(e) => return Err(e),
+    };
+    if let Some(years) = with_module(&years, &module_name(year)) {
+        fs::write(years_path, years)?;
+    }
+
+    let lib = fs::read_to_string(LIB_PATH)?;
+    if let Some(lib) = with_module(&lib, "years") {
+        fs::write(LIB_PATH, lib)?;
+    }
+
+    Ok(true)
+}
+
+/// Declares `pub mod {name};`, sorted into the existing declarations. Returns [`None`] if it is
+/// already declared.
+fn with_module(contents: &str, name: &str) -> Option<String> {
+    let declaration = format!("pub mod {name};");
+    let mut lines: Vec<&str> = contents.lines().collect();
+    if lines.iter().any(|line| line.trim() == declaration) {
+        return None;
+    }
+
+    let declarations: Vec<usize> = (0..lines.len())
+        .filter(|&i| lines[i].starts_with("pub mod ") && lines[i].ends_with(';'))
+        .collect();
+    // after the last declaration that sorts before it, or in front of the first one.
+    match declarations
+        .iter()
+        .rfind(|&&i| lines[i] < declaration.as_str())
+        .or(declarations.first())
+    {
+        Some(&i) if lines[i] < declaration.as_str() => lines.insert(i + 1, &declaration),
+        Some(&i) => lines.insert(i, &declaration),
+        // separate the first declaration from a leading comment.
+        None => {
+            if lines.last().is_some_and(|line| !line.trim().is_empty()) {
+                lines.push("");
+            }
+            lines.push(&declaration);
+        }
+    }
+    Some(lines.join("\n") + "\n")
+}
+
+/// Adds the import of the module of `year` after the imports of a rendered solution. It allows
+/// being unused, as most days do not use the module.
+pub fn with_import(module: &str, year: u16) -> String {
+    let import = import(year);
+    if module.lines().any(|line| line.trim() == import) {
+        return module.to_string();
+    }
+
+    let mut lines: Vec<&str> = module.lines().collect();
+    let index = lines
+        .iter()
+        .rposition(|line| line.starts_with("use "))
+        .map_or(0, |last| last + 1);
+    lines.splice(index..index, ["#[allow(unused_imports)]", &import]);
+    lines.join("\n") + "\n"
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{with_import, with_module};
+
+    #[test]
+    fn declares_modules() {
+        let lib = "pub mod template;\n\n// helpers\npub mod grid;\npub mod segments;\n";
+        assert_eq!(
+            with_module(lib, "years").unwrap(),
+            "pub mod template;\n\n// helpers\npub mod grid;\npub mod segments;\npub mod years;\n"
+        );
+        assert_eq!(
+            with_module(lib, "ocr").unwrap(),
+            "pub mod template;\n\n// helpers\npub mod grid;\npub mod ocr;\npub mod segments;\n"
+        );
+        assert_eq!(with_module(lib, "grid"), None);
+        assert_eq!(
+            with_module("//! comment\n", "y2019").unwrap(),
+            "//! comment\n\npub mod y2019;\n"
+        );
+    }
+
+    #[test]
+    fn imports_year_modules() {
+        let module = "//! title\nuse advent_of_code::template::answer::Answer;\n\nfn main() {}\n";
+        let expected = "//! title\nuse advent_of_code::template::answer::Answer;\n\
+            #[allow(unused_imports)]\nuse advent_of_code::years::y2019;\n\nfn main() {}\n";
+        assert_eq!(with_import(module, 2019), expected);
+        assert_eq!(with_import(expected, 2019), expected);
+        assert_eq!(
+            with_import("fn main() {}\n", 2019),
+            "#[allow(unused_imports)]\nuse advent_of_code::years::y2019;\nfn main() {}\n"
+        );
+    }
+}
@@ -0,0 +1,291 @@
+/// Records frames of a solution's state, e.g. a grid with highlighted cells, for debugging.
+///
+/// Recording only happens in builds with the `visualize` feature that run with `--visualize`,
+/// which `cargo solve <day> --visualize` does. Otherwise [`record`] does nothing, and without
+/// the feature it compiles to nothing, so benchmarks are not affected. Frames are built in a
+/// closure that only runs while recording:
+///
+/// ```ignore
+/// visualize::record(|| Frame::grid(&grid).highlight([pos], Color::Red));
+/// ```
+use crate::grid::{Grid, Pos};
+
+#[cfg(feature = "visualize")]
+use std::sync::{Mutex, OnceLock};
+
+#[cfg(feature = "visualize")]
+static FRAMES: Mutex<Vec<Frame>> = Mutex::new(vec![]);
+
+/// The color of highlighted cells.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+}
+
+impl Color {
+    /// The ANSI escape code that sets this color as background.
+    fn ansi(self) -> &'static str {
+        match self {
+            Color::Red => "\x1b[41m",
+            Color::Green => "\x1b[42m",
+            Color::Yellow => "\x1b[43m",
+            Color::Blue => "\x1b[44m",
+            Color::Magenta => "\x1b[45m",
+            Color::Cyan => "\x1b[46m",
+        }
+    }
+
+    fn rgb(self) -> [u8; 3] {
+        match self {
+            Color::Red => [220, 50, 47],
+            Color::Green => [80, 200, 70],
+            Color::Yellow => [240, 200, 40],
+            Color::Blue => [50, 110, 230],
+            Color::Magenta => [210, 60, 200],
+            Color::Cyan => [40, 200, 210],
+        }
+    }
+}
+
+/// A picture of characters, some of them highlighted, with an optional caption.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    glyphs: Vec<char>,
+    colors: Vec<Option<Color>>,
+    /// The point shown in the top left corner of frames of points.
+    origin: (i64, i64),
+    caption: String,
+}
+
+impl Frame {
+    fn new(width: usize, height: usize, glyphs: Vec<char>, origin: (i64, i64)) -> Self {
+        Self {
+            width,
+            height,
+            glyphs,
+            colors: vec![None; width * height],
+            origin,
+            caption: String::new(),
+        }
+    }
+
+    /// Shows every cell of `grid` as its character.
+    pub fn grid(grid: &Grid<u8>) -> Self {
+        Self::grid_with(grid, |cell| char::from(*cell))
+    }
+
+    /// Shows every cell of `grid` as the character returned by `glyph`.
+    pub fn grid_with<T>(grid: &Grid<T>, glyph: impl Fn(&T) -> char) -> Self {
+        let glyphs = grid.iter().map(|(_, cell)| glyph(cell)).collect();
+        Self::new(grid.width(), grid.height(), glyphs, (0, 0))
+    }
+
+    /// Shows the lines of `text`, padding short lines with spaces.
+    pub fn text(text: &str) -> Self {
+        let width = text
+            .lines()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        let glyphs: Vec<char> = text
+            .lines()
+            .flat_map(|line| line.chars().chain(std::iter::repeat(' ')).take(width))
+            .collect();
+        let height = text.lines().count();
+        Self::new(width, height, glyphs, (0, 0))
+    }
+
+    /// Shows `points` as `#` within their bounding box, with `x` growing to the right and `y`
+    /// growing downwards.
+    pub fn points(points: impl IntoIterator<Item = (i64, i64)>) -> Self {
+        let points: Vec<(i64, i64)> = points.into_iter().collect();
+        let (Some(min_x), Some(max_x)) = (
+            points.iter().map(|p| p.0).min(),
+            points.iter().map(|p| p.0).max(),
+        ) else {
+            return Self::new(0, 0, vec![], (0, 0));
+        };
+        let min_y = points.iter().map(|p| p.1).min().unwrap();
+        let max_y = points.iter().map(|p| p.1).max().unwrap();
+
+        let width = (max_x - min_x + 1) as usize;
+        let height = (max_y - min_y + 1) as usize;
+        let mut frame = Self::new(width, height, vec!['.'; width * height], (min_x, min_y));
+        for point in points {
+            if let Some(i) = frame.point_index(point) {
+                frame.glyphs[i] = '#';
+            }
+        }
+        frame
+    }
+
+    /// Highlights the cells at `positions`. Positions outside of the frame are ignored.
+    pub fn highlight(mut self, positions: impl IntoIterator<Item = Pos>, color: Color) -> Self {
+        for pos in positions {
+            if pos.x < self.width && pos.y < self.height {
+                self.colors[pos.y * self.width + pos.x] = Some(color);
+            }
+        }
+        self
+    }
+
+    /// Highlights `points` of a frame created with [`Frame::points`].
+    pub fn highlight_points(
+        mut self,
+        points: impl IntoIterator<Item = (i64, i64)>,
+        color: Color,
+    ) -> Self {
+        for point in points {
+            if let Some(i) = self.point_index(point) {
+                self.colors[i] = Some(color);
+            }
+        }
+        self
+    }
+
+    /// Shows `caption` below the frame, e.g. the current step.
+    pub fn caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = caption.into();
+        self
+    }
+
+    fn point_index(&self, (x, y): (i64, i64)) -> Option<usize> {
+        let x = usize::try_from(x - self.origin.0).ok()?;
+        let y = usize::try_from(y - self.origin.1).ok()?;
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+
+    /// The lines of the frame with ANSI colors, followed by the caption if there is one.
+    pub(crate) fn render_text(&self) -> String {
+        let mut text = String::new();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let i = y * self.width + x;
+                match self.colors[i] {
+                    Some(color) => {
+                        text.push_str(&format!("{}{}\x1b[0m", color.ansi(), self.glyphs[i]))
+                    }
+                    None => text.push(self.glyphs[i]),
+                }
+            }
+            text.push('\n');
+        }
+        if !self.caption.is_empty() {
+            text.push_str(&self.caption);
+            text.push('\n');
+        }
+        text
+    }
+
+    /// The frame as an image with square cells of `scale` pixels. Returns the width, height and
+    /// the RGB pixels row by row.
+    pub(crate) fn render_pixels(&self, scale: usize) -> (usize, usize, Vec<u8>) {
+        let (width, height) = (self.width * scale, self.height * scale);
+        let mut pixels = Vec::with_capacity(width * height * 3);
+        for y in 0..height {
+            for x in 0..width {
+                let i = (y / scale) * self.width + x / scale;
+                let rgb = match self.colors[i] {
+                    Some(color) => color.rgb(),
+                    None => glyph_rgb(self.glyphs[i]),
+                };
+                pixels.extend(rgb);
+            }
+        }
+        (width, height, pixels)
+    }
+}
+
+/// Walls are light, empty cells dark and digits (e.g. heights) shades of gray.
+fn glyph_rgb(glyph: char) -> [u8; 3] {
+    match glyph {
+        '#' | '█' => [210, 210, 210],
+        '.' | ' ' => [24, 24, 24],
+        '0'..='9' => {
+            let shade = 40 + (glyph as u8 - b'0') * 20;
+            [shade; 3]
+        }
+        _ => [110, 110, 110],
+    }
+}
+
+/// Whether frames are recorded: the solution was built with the `visualize` feature and runs
+/// with `--visualize`. Use it to skip work that only serves the visualization.
+#[inline(always)]
+pub fn enabled() -> bool {
+    #[cfg(feature = "visualize")]
+    {
+        static ENABLED: OnceLock<bool> = OnceLock::new();
+        *ENABLED.get_or_init(|| std::env::args().any(|arg| arg == "--visualize"))
+    }
+    #[cfg(not(feature = "visualize"))]
+    {
+        false
+    }
+}
+
+/// Records the frame built by `frame` if recording is [`enabled`].
+#[inline(always)]
+pub fn record(frame: impl FnOnce() -> Frame) {
+    #[cfg(feature = "visualize")]
+    {
+        if enabled() {
+            FRAMES.lock().unwrap().push(frame());
+        }
+    }
+    #[cfg(not(feature = "visualize"))]
+    {
+        let _ = frame;
+    }
+}
+
+/// Takes the frames recorded so far.
+pub(crate) fn take_frames() -> Vec<Frame> {
+    #[cfg(feature = "visualize")]
+    {
+        std::mem::take(&mut *FRAMES.lock().unwrap())
+    }
+    #[cfg(not(feature = "visualize"))]
+    {
+        vec![]
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Color, Frame};
+    use crate::grid::Pos;
+
+    #[test]
+    fn renders_text() {
+        let frame = Frame::text("#.\n.")
+            .highlight([Pos::new(1, 1), Pos::new(5, 5)], Color::Red)
+            .caption("step 1");
+        assert_eq!(frame.render_text(), "#.\n.\x1b[41m \x1b[0m\nstep 1\n");
+    }
+
+    #[test]
+    fn renders_points() {
+        let frame = Frame::points([(-1, 2), (1, 3)]).highlight_points([(1, 3)], Color::Green);
+        assert_eq!(frame.render_text(), "#..\n..\x1b[42m#\x1b[0m\n");
+        assert_eq!(Frame::points([]).render_text(), "");
+    }
+
+    #[test]
+    fn renders_pixels() {
+        let (width, height, pixels) = Frame::text("#.").render_pixels(2);
+        assert_eq!((width, height), (4, 2));
+        assert_eq!(&pixels[..6], &[210, 210, 210, 210, 210, 210]);
+        assert_eq!(&pixels[6..9], &[24, 24, 24]);
+        assert_eq!(pixels.len(), 4 * 2 * 3);
+    }
+}
@@ -0,0 +1,4 @@
+//! Modules shared by the solutions of one year, e.g. the Intcode computer of 2019.
+//!
+//! `cargo scaffold <day> --year-module` creates the module of a year, which scaffolded solutions
+//! of that year then import as `advent_of_code::years::y2019`.